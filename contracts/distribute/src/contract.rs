@@ -1,22 +1,28 @@
 use crate::error::ContractError;
-use crate::handler::{add_rule_config, claim, update_config, update_rule_config};
-use crate::helper::BASE_RATE_12;
+use crate::handler::{
+    add_rule_config, build_rule_config, claim, update_config, update_rule_config,
+};
+use crate::helper::single_segment_tranches;
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use crate::querier::{query_claimable_info, query_config, query_rule_info};
 use crate::state::{
-    store_distribute_config, store_rule_config, store_rule_config_state, DistributeConfig,
-    RuleConfig, RuleConfigState,
+    read_legacy_rule_config, read_rule_config_state, read_rule_types, store_distribute_config,
+    store_rule_config, store_rule_config_state, DistributeConfig, RuleConfig, RuleConfigState,
 };
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+    attr, to_binary, Attribute, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError,
+    StdResult, Storage,
 };
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
+use semver::Version;
 
 // version info for migration info
 const CONTRACT_NAME: &str = "kryptonite.finance:seilor-distribute";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+// first version storing rules as tranches
+const TRANCHES_VERSION: Version = Version::new(0, 2, 0);
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -30,31 +36,12 @@ pub fn instantiate(
     // init rule config && state
     let mut rule_total_amount = 0u128;
     for (rule_type, rule_msg) in msg.rule_configs_map {
-        rule_total_amount += rule_msg.rule_total_amount.clone();
-        let end_linear_release_time =
-            rule_msg.start_linear_release_time + rule_msg.unlock_linear_release_time;
-        let linear_release_per_second = rule_msg.unlock_linear_release_amount * BASE_RATE_12
-            / u128::from(rule_msg.unlock_linear_release_time);
-        let rule_config = RuleConfig {
-            rule_name: rule_msg.rule_name,
-            rule_owner: rule_msg.rule_owner,
-            rule_total_amount: rule_msg.rule_total_amount,
-            start_release_amount: rule_msg.start_release_amount,
-            lock_start_time: rule_msg.lock_start_time,
-            lock_end_time: rule_msg.lock_end_time,
-            start_linear_release_time: rule_msg.start_linear_release_time,
-            end_linear_release_time,
-            unlock_linear_release_amount: rule_msg.unlock_linear_release_amount,
-            unlock_linear_release_time: rule_msg.unlock_linear_release_time,
-            linear_release_per_second,
-        };
+        rule_total_amount += rule_msg.rule_total_amount;
+        let rule_config = build_rule_config(rule_msg)?;
         store_rule_config(deps.storage, &rule_type, &rule_config)?;
 
         let rule_config_state = RuleConfigState {
-            is_start_release: false,
             claimed_amount: 0u128,
-            released_amount: 0u128,
-            last_claim_linear_release_time: 0,
         };
         store_rule_config_state(deps.storage, &rule_type, &rule_config_state)?;
     }
@@ -124,6 +111,51 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    let contract_version = get_contract_version(deps.storage)?;
+    if contract_version.contract != CONTRACT_NAME {
+        return Err(StdError::generic_err(
+            "Can only migrate from the same contract",
+        ));
+    }
+    let stored_version: Version = contract_version
+        .version
+        .parse()
+        .map_err(|_| StdError::generic_err("Invalid stored contract version"))?;
+
+    let mut attrs = vec![attr("action", "migrate")];
+    // every rule stored before tranches is a single-segment rule, move it onto its tranches
+    if stored_version < TRANCHES_VERSION {
+        attrs.extend(migrate_legacy_rules(deps.storage)?);
+    }
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::new().add_attributes(attrs))
+}
+
+fn migrate_legacy_rules(storage: &mut dyn Storage) -> StdResult<Vec<Attribute>> {
+    let mut attrs = vec![];
+    for rule_type in read_rule_types(storage)? {
+        let legacy_rule_config = read_legacy_rule_config(storage, &rule_type)?;
+        let rule_config = RuleConfig {
+            rule_name: legacy_rule_config.rule_name,
+            rule_owner: legacy_rule_config.rule_owner,
+            rule_total_amount: legacy_rule_config.rule_total_amount,
+            // lock_end_time was stored but never enforced, so it's dropped
+            lock_start_time: legacy_rule_config.lock_start_time,
+            tranches: single_segment_tranches(
+                legacy_rule_config.start_release_amount,
+                legacy_rule_config.lock_start_time,
+                legacy_rule_config.start_linear_release_time,
+                legacy_rule_config.end_linear_release_time,
+                legacy_rule_config.unlock_linear_release_amount,
+            ),
+        };
+        store_rule_config(storage, &rule_type, &rule_config)?;
+
+        // only claimed_amount is kept from the legacy state
+        let rule_config_state = read_rule_config_state(storage, &rule_type)?;
+        store_rule_config_state(storage, &rule_type, &rule_config_state)?;
+        attrs.push(attr("rule_type", rule_type));
+    }
+    Ok(attrs)
 }
@@ -1,15 +1,15 @@
 use crate::error::ContractError;
-use crate::helper::BASE_RATE_12;
+use crate::helper::single_segment_tranches;
 use crate::msg::{RuleConfigMsg, UpdateRuleConfigMsg};
 use crate::querier::query_claimable_info;
 use crate::state::{
     check_rule_config_exist, read_distribute_config, read_rule_config, read_rule_config_state,
     store_distribute_config, store_rule_config, store_rule_config_state, RuleConfig,
-    RuleConfigState,
+    RuleConfigState, Tranche,
 };
 use cosmwasm_std::{
     attr, to_binary, Addr, Binary, CosmosMsg, DepsMut, Env, MessageInfo, Response, StdError,
-    Uint128, WasmMsg,
+    StdResult, Uint128, WasmMsg,
 };
 
 pub fn claim(
@@ -43,17 +43,11 @@ pub fn claim(
 
     let claim_amount = claimable_info.can_claim_amount;
 
-    if claimable_info.release_amount > 0 && !rule_config_state.is_start_release {
-        //update the start release state
-        rule_config_state.is_start_release = true;
-        rule_config_state.released_amount += claimable_info.release_amount;
-    }
-
     rule_config_state.claimed_amount += claim_amount;
     if rule_config_state.claimed_amount > rule_config.rule_total_amount {
         return Err(ContractError::AmountClaimOverTotal(
-            rule_config_state.claimed_amount.clone(),
-            rule_config.rule_total_amount.clone(),
+            rule_config_state.claimed_amount,
+            rule_config.rule_total_amount,
         ));
     }
 
@@ -64,7 +58,7 @@ pub fn claim(
         // send the claim amount to user
         let seilor_mint_msg = seilor::msg::ExecuteMsg::Mint {
             recipient: claim_user.clone().to_string(),
-            amount: Uint128::from(claim_amount.clone()),
+            amount: Uint128::from(claim_amount),
             contract: Option::from(claim_user.clone().to_string()),
             msg,
         };
@@ -161,7 +155,7 @@ pub fn add_rule_config(
         )));
     }
 
-    if rule_msg.rule_total_amount <= 0 {
+    if rule_msg.rule_total_amount == 0 {
         return Err(ContractError::Std(StdError::generic_err(
             "rule total amount must be greater than zero",
         )));
@@ -175,35 +169,16 @@ pub fn add_rule_config(
         )));
     }
 
-    let end_linear_release_time =
-        rule_msg.start_linear_release_time + rule_msg.unlock_linear_release_time;
-    let linear_release_per_second = rule_msg.unlock_linear_release_amount * BASE_RATE_12
-        / u128::from(rule_msg.unlock_linear_release_time);
+    let rule_config = build_rule_config(rule_msg)?;
 
     distribute_config.rules_total_amount = rule_total_amount;
 
     store_distribute_config(deps.storage, &distribute_config)?;
 
-    let rule_config = RuleConfig {
-        rule_name: rule_msg.rule_name,
-        rule_owner: rule_msg.rule_owner,
-        rule_total_amount: rule_msg.rule_total_amount,
-        start_release_amount: rule_msg.start_release_amount,
-        lock_start_time: rule_msg.lock_start_time,
-        lock_end_time: rule_msg.lock_end_time,
-        start_linear_release_time: rule_msg.start_linear_release_time,
-        end_linear_release_time,
-        unlock_linear_release_amount: rule_msg.unlock_linear_release_amount,
-        unlock_linear_release_time: rule_msg.unlock_linear_release_time,
-        linear_release_per_second,
-    };
     store_rule_config(deps.storage, &rule_type, &rule_config)?;
 
     let rule_config_state = RuleConfigState {
-        is_start_release: false,
         claimed_amount: 0u128,
-        released_amount: 0u128,
-        last_claim_linear_release_time: 0,
     };
     store_rule_config_state(deps.storage, &rule_type, &rule_config_state)?;
 
@@ -212,3 +187,95 @@ pub fn add_rule_config(
         ("rule_type", rule_type.as_str()),
     ]))
 }
+
+/// Builds a rule from its message, a rule without tranches becomes its single-segment tranches.
+pub fn build_rule_config(rule_msg: RuleConfigMsg) -> StdResult<RuleConfig> {
+    // never enforced by claims, so a rule can't be given a lock it won't get
+    if rule_msg.lock_end_time != 0 {
+        return Err(StdError::generic_err(
+            "lock_end_time is deprecated and must be 0",
+        ));
+    }
+    let tranches = match rule_msg.tranches {
+        Some(tranches) => {
+            if rule_msg.start_release_amount != 0 || rule_msg.unlock_linear_release_amount != 0 {
+                return Err(StdError::generic_err(
+                    "tranches can't be combined with the single segment release",
+                ));
+            }
+            validate_tranches(&tranches, rule_msg.rule_total_amount)?;
+            tranches
+        }
+        None => {
+            let release_amount = rule_msg
+                .start_release_amount
+                .checked_add(rule_msg.unlock_linear_release_amount)
+                .ok_or_else(|| StdError::generic_err("release amounts overflow"))?;
+            if release_amount > rule_msg.rule_total_amount {
+                return Err(StdError::generic_err(
+                    "release amounts must not exceed rule total amount",
+                ));
+            }
+            single_segment_tranches(
+                rule_msg.start_release_amount,
+                rule_msg.lock_start_time,
+                rule_msg.start_linear_release_time,
+                rule_msg.start_linear_release_time + rule_msg.unlock_linear_release_time,
+                rule_msg.unlock_linear_release_amount,
+            )
+        }
+    };
+
+    Ok(RuleConfig {
+        rule_name: rule_msg.rule_name,
+        rule_owner: rule_msg.rule_owner,
+        rule_total_amount: rule_msg.rule_total_amount,
+        lock_start_time: rule_msg.lock_start_time,
+        tranches,
+    })
+}
+
+fn validate_tranches(tranches: &[Tranche], rule_total_amount: u128) -> StdResult<()> {
+    if tranches.is_empty() {
+        return Err(StdError::generic_err("tranches must not be empty"));
+    }
+
+    let mut tranches_amount = 0u128;
+    let mut last_start_time = 0u64;
+    for tranche in tranches {
+        let (start_time, amount) = match *tranche {
+            Tranche::Cliff {
+                release_time,
+                amount,
+            } => (release_time, amount),
+            Tranche::Linear {
+                start_time,
+                end_time,
+                amount,
+            } => {
+                if start_time >= end_time {
+                    return Err(StdError::generic_err(
+                        "linear tranche must end after it starts",
+                    ));
+                }
+                (start_time, amount)
+            }
+        };
+        if start_time < last_start_time {
+            return Err(StdError::generic_err(
+                "tranches must be ordered by start time",
+            ));
+        }
+        last_start_time = start_time;
+        tranches_amount = tranches_amount
+            .checked_add(amount)
+            .ok_or_else(|| StdError::generic_err("tranche amounts overflow"))?;
+    }
+
+    if tranches_amount != rule_total_amount {
+        return Err(StdError::generic_err(
+            "tranche amounts must add up to rule total amount",
+        ));
+    }
+    Ok(())
+}
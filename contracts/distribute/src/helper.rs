@@ -1,2 +1,60 @@
-// pub const BASE_RATE_6: u128 = 1000000u128;
-pub const BASE_RATE_12: u128 = 1000000000000u128;
+use crate::state::Tranche;
+use cosmwasm_std::Uint128;
+
+/// Tranches of a single-segment rule: the start release unlocks at lock_start_time, the linear
+/// release runs from start_linear_release_time to end_linear_release_time.
+pub fn single_segment_tranches(
+    start_release_amount: u128,
+    lock_start_time: u64,
+    start_linear_release_time: u64,
+    end_linear_release_time: u64,
+    unlock_linear_release_amount: u128,
+) -> Vec<Tranche> {
+    let mut tranches = vec![];
+    if start_release_amount > 0 {
+        tranches.push(Tranche::Cliff {
+            release_time: lock_start_time,
+            amount: start_release_amount,
+        });
+    }
+    if unlock_linear_release_amount > 0 {
+        tranches.push(Tranche::Linear {
+            start_time: start_linear_release_time,
+            end_time: end_linear_release_time,
+            amount: unlock_linear_release_amount,
+        });
+    }
+    tranches
+}
+
+/// Amounts unlocked at block_time by the cliff tranches and by the linear tranches.
+pub fn unlocked_amounts(tranches: &[Tranche], block_time: u64) -> (u128, u128) {
+    let mut release_amount = 0u128;
+    let mut linear_release_amount = 0u128;
+    for tranche in tranches {
+        match *tranche {
+            Tranche::Cliff {
+                release_time,
+                amount,
+            } => {
+                if block_time >= release_time {
+                    release_amount += amount;
+                }
+            }
+            Tranche::Linear {
+                start_time,
+                end_time,
+                amount,
+            } => {
+                if block_time >= end_time {
+                    linear_release_amount += amount;
+                } else if block_time > start_time {
+                    linear_release_amount += Uint128::from(amount)
+                        .multiply_ratio(block_time - start_time, end_time - start_time)
+                        .u128();
+                }
+            }
+        }
+    }
+    (release_amount, linear_release_amount)
+}
@@ -1,4 +1,4 @@
-use crate::state::{RuleConfig, RuleConfigState};
+use crate::state::{RuleConfig, RuleConfigState, Tranche};
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Binary};
 use std::collections::HashMap;
@@ -31,17 +31,27 @@ pub struct UpdateRuleConfigMsg {
     pub rule_owner: Option<Addr>,
 }
 
+/// Either a single-segment rule, a start release at lock_start_time plus one linear release,
+/// or `tranches`, which then replace the single-segment fields.
 #[cw_serde]
 pub struct RuleConfigMsg {
     pub rule_name: String,
     pub rule_owner: Addr,
     pub rule_total_amount: u128,
+    #[serde(default)]
     pub start_release_amount: u128,
     pub lock_start_time: u64,
+    // deprecated, must be 0, nothing is claimable before lock_start_time and the tranches
+    #[serde(default)]
     pub lock_end_time: u64,
+    #[serde(default)]
     pub start_linear_release_time: u64,
+    #[serde(default)]
     pub unlock_linear_release_amount: u128,
+    #[serde(default)]
     pub unlock_linear_release_time: u64,
+    // ordered by start time, amounts must add up to rule_total_amount
+    pub tranches: Option<Vec<Tranche>>,
 }
 
 #[cw_serde]
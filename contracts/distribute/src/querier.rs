@@ -1,4 +1,4 @@
-use crate::helper::BASE_RATE_12;
+use crate::helper::unlocked_amounts;
 use crate::msg::{QueryClaimableInfoResponse, QueryConfigResponse, QueryRuleInfoResponse};
 use crate::state::{read_distribute_config, read_rule_config, read_rule_config_state};
 use cosmwasm_std::{Deps, Env, StdResult};
@@ -31,53 +31,11 @@ pub fn query_claimable_info(
         });
     }
 
-    let mut release_amount = 0u128;
-    let mut linear_release_amount = 0u128;
-
-    //Calculate the start release amount
-    if rule_config.start_release_amount != 0 {
-        release_amount = rule_config.start_release_amount;
-        //update the start release state
-    }
-
-    //Calculate the linear release amount
-    if block_time > rule_config.start_linear_release_time {
-        let start_calc_time = if rule_config_state.last_claim_linear_release_time
-            > rule_config.start_linear_release_time
-        {
-            rule_config_state.last_claim_linear_release_time
-        } else {
-            rule_config.start_linear_release_time
-        };
-
-        if block_time > rule_config.end_linear_release_time {
-            if rule_config_state.is_start_release {
-                linear_release_amount = rule_config.unlock_linear_release_amount
-                    + rule_config.start_release_amount
-                    - rule_config_state.released_amount;
-            } else {
-                linear_release_amount =
-                    rule_config.unlock_linear_release_amount - rule_config_state.released_amount;
-            }
-        } else {
-            let diff_time = block_time - start_calc_time;
-            linear_release_amount =
-                u128::from(diff_time) * rule_config.linear_release_per_second / BASE_RATE_12;
-        }
-
-        // let start_time = if block_time > rule_config.end_linear_release_time {
-        //     rule_config.end_linear_release_time
-        // } else {
-        //     block_time
-        // };
-        //
-        // let diff_time = start_time - start_calc_time;
-        //
-        // linear_release_amount =
-        //     u128::from(diff_time) * rule_config.linear_release_per_second / BASE_RATE_12;
-    }
-    let can_claim_amount =
-        release_amount + linear_release_amount - rule_config_state.claimed_amount;
+    let (release_amount, linear_release_amount) =
+        unlocked_amounts(&rule_config.tranches, block_time);
+    let can_claim_amount = (release_amount + linear_release_amount)
+        .min(rule_config.rule_total_amount)
+        .saturating_sub(rule_config_state.claimed_amount);
 
     Ok(QueryClaimableInfoResponse {
         can_claim_amount,
@@ -1,4 +1,4 @@
-use cosmwasm_std::{Addr, StdResult, Storage};
+use cosmwasm_std::{Addr, Order, StdError, StdResult, Storage};
 use cw_storage_plus::{Item, Map};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -11,8 +11,41 @@ pub struct DistributeConfig {
     pub rules_total_amount: u128,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Tranche {
+    // unlocks amount at release_time
+    Cliff {
+        release_time: u64,
+        amount: u128,
+    },
+    // unlocks amount linearly from start_time to end_time
+    Linear {
+        start_time: u64,
+        end_time: u64,
+        amount: u128,
+    },
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct RuleConfig {
+    pub rule_name: String,
+    pub rule_owner: Addr,
+    pub rule_total_amount: u128,
+    // nothing can be claimed before, 0 for no lock
+    pub lock_start_time: u64,
+    // ordered by start time
+    pub tranches: Vec<Tranche>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RuleConfigState {
+    pub claimed_amount: u128,
+}
+
+// RuleConfig as stored before tranches, only read by migrate
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyRuleConfig {
     pub rule_name: String,
     pub rule_owner: Addr,
     pub rule_total_amount: u128,
@@ -26,18 +59,12 @@ pub struct RuleConfig {
     pub linear_release_per_second: u128,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct RuleConfigState {
-    pub is_start_release: bool,
-    pub released_amount: u128,
-    pub claimed_amount: u128,
-    pub last_claim_linear_release_time: u64,
-}
-
 const DISTRIBUTE_CONFIG: Item<DistributeConfig> = Item::new("distribute_config");
 
 const RULE_CONFIG: Map<&str, RuleConfig> = Map::new("rule_config");
 
+const LEGACY_RULE_CONFIG: Map<&str, LegacyRuleConfig> = Map::new("rule_config");
+
 const RULE_CONFIG_STATE: Map<&str, RuleConfigState> = Map::new("rule_config_state");
 
 pub fn store_distribute_config(
@@ -63,6 +90,18 @@ pub fn read_rule_config(storage: &dyn Storage, key: &str) -> StdResult<RuleConfi
     RULE_CONFIG.load(storage, key)
 }
 
+// raw keys, so rules still stored in the legacy layout are listed too
+pub fn read_rule_types(storage: &dyn Storage) -> StdResult<Vec<String>> {
+    RULE_CONFIG
+        .keys_raw(storage, None, None, Order::Ascending)
+        .map(|key| String::from_utf8(key).map_err(StdError::from))
+        .collect()
+}
+
+pub fn read_legacy_rule_config(storage: &dyn Storage, key: &str) -> StdResult<LegacyRuleConfig> {
+    LEGACY_RULE_CONFIG.load(storage, key)
+}
+
 pub fn store_rule_config_state(
     storage: &mut dyn Storage,
     key: &str,
@@ -1,11 +1,11 @@
 use crate::contract::{execute, instantiate, query};
-use crate::helper::BASE_RATE_12;
 use crate::msg::{
     ExecuteMsg, InstantiateMsg, QueryClaimableInfoResponse, QueryMsg, QueryRuleInfoResponse,
 };
 use crate::testing::mock_fn::{
     mock_instantiate_msg, CREATOR, DAO_OWNER, COMMUNITY_OFFERING_OWNER, RESERVE_OWNER, TEAM_OWNER, MINING_OWNER,
 };
+use crate::state::Tranche;
 use crate::testing::mock_third_fn::mock_seilor_instantiate_msg;
 use cosmwasm_std::testing::mock_env;
 use cosmwasm_std::{Addr, Coin, Timestamp, Uint128};
@@ -23,11 +23,14 @@ fn mock_app(owner: Addr, coins: Vec<Coin>, block_time: Option<u64>) -> App {
 }
 
 fn sstore_seilor_contract(app: &mut App) -> u64 {
-    let seilor_contract = Box::new(ContractWrapper::new_with_empty(
-        seilor::contract::execute,
-        seilor::contract::instantiate,
-        seilor::contract::query,
-    ));
+    let seilor_contract = Box::new(
+        ContractWrapper::new(
+            seilor::contract::execute,
+            seilor::contract::instantiate,
+            seilor::contract::query,
+        )
+        .with_reply(seilor::contract::reply),
+    );
     app.store_code(seilor_contract)
 }
 
@@ -183,26 +186,40 @@ fn test_integration() {
         block.height += 1000000u64;
     });
 
+    let (
+        start_release_amount,
+        start_linear_release_time,
+        end_linear_release_time,
+        unlock_linear_release_amount,
+    ) = match (&rule_config.tranches[0], &rule_config.tranches[1]) {
+        (
+            Tranche::Cliff { amount, .. },
+            Tranche::Linear {
+                start_time,
+                end_time,
+                amount: linear_amount,
+            },
+        ) => (*amount, *start_time, *end_time, *linear_amount),
+        _ => panic!("unexpected tranches"),
+    };
+
     let res = query_claimable_info(&mut app, &seilor_distribute, &rule_type);
 
-    assert_eq!(res.can_claim_amount, rule_config.start_release_amount);
-    assert_eq!(res.release_amount, rule_config.start_release_amount);
+    assert_eq!(res.can_claim_amount, start_release_amount);
+    assert_eq!(res.release_amount, start_release_amount);
 
     // update block to lock end time
     app.update_block(|block| {
-        block.time = Timestamp::from_seconds(rule_config.start_linear_release_time + 1000000u64);
+        block.time = Timestamp::from_seconds(start_linear_release_time + 1000000u64);
         block.height += 1000000u64;
     });
-    let per_release_second = rule_config.unlock_linear_release_amount * BASE_RATE_12
-        / (rule_config.end_linear_release_time - rule_config.start_linear_release_time) as u128;
-
-    assert_eq!(per_release_second, rule_config.linear_release_per_second);
     let res = query_claimable_info(&mut app, &seilor_distribute, &rule_type);
-    let cal_total_release_amount = per_release_second * 1000000u64 as u128 / BASE_RATE_12;
+    let cal_total_release_amount = unlock_linear_release_amount * 1000000u64 as u128
+        / (end_linear_release_time - start_linear_release_time) as u128;
     assert_eq!(res.linear_release_amount, cal_total_release_amount);
     assert_eq!(
         res.can_claim_amount,
-        rule_config.start_release_amount + cal_total_release_amount
+        start_release_amount + cal_total_release_amount
     );
     let loot_box_owner = Addr::unchecked(COMMUNITY_OFFERING_OWNER.clone().to_string());
 
@@ -218,21 +235,21 @@ fn test_integration() {
 
     assert_eq!(
         res.balance.u128(),
-        rule_config.start_release_amount + cal_total_release_amount
+        start_release_amount + cal_total_release_amount
     );
 
     let res = query_claimable_info(&mut app, &seilor_distribute, &rule_type);
     assert_eq!(res.can_claim_amount, 0u128);
     // update block to end time
     app.update_block(|block| {
-        block.time = Timestamp::from_seconds(rule_config.start_linear_release_time + 31622400u64);
+        block.time = Timestamp::from_seconds(start_linear_release_time + 31622400u64);
         block.height += 1000000u64;
     });
     let res_end_time = query_claimable_info(&mut app, &seilor_distribute, &rule_type);
     // update block to end time
     app.update_block(|block| {
         block.time = Timestamp::from_seconds(
-            rule_config.start_linear_release_time + 31622399u64 + 1000000u64,
+            start_linear_release_time + 31622399u64 + 1000000u64,
         );
         block.height += 1000000u64;
     });
@@ -250,7 +267,7 @@ fn test_integration() {
     let res = get_seilor_token_info(&mut app, &seilor_token);
     assert_eq!(
         res.total_supply.u128(),
-        rule_config.start_release_amount + cal_total_release_amount
+        start_release_amount + cal_total_release_amount
     );
 }
 
@@ -330,15 +347,14 @@ fn update_distribute_contract_to_seilor(
     seilor_token: &Addr,
     distribute: &Addr,
 ) {
-    let update_seilor_config_msg = seilor::msg::ExecuteMsg::UpdateConfig {
-        fund: None,
-        gov: None,
-        distribute: Some(distribute.clone()),
+    let set_seilor_minter_msg = seilor::msg::ExecuteMsg::SetMinter {
+        minter: distribute.to_string(),
+        quota: Uint128::from(1_000_000_000_000_000u128),
     };
     let res = app.execute_contract(
         creator.clone(),
         seilor_token.clone(),
-        &update_seilor_config_msg,
+        &set_seilor_minter_msg,
         &[], // no funds
     );
     assert!(res.is_ok());
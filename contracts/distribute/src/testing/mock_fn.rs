@@ -27,10 +27,11 @@ pub fn mock_instantiate_msg(distribute_token: Addr) -> InstantiateMsg {
             rule_total_amount: 175_000_000_000_000u128,
             start_release_amount: 35_000_000_000_000u128,
             lock_start_time: 1688366468u64,
-            lock_end_time: 1696315268u64,
+            lock_end_time: 0,
             start_linear_release_time: 1696315269u64,
             unlock_linear_release_amount: 140_000_000_000_000u128,
             unlock_linear_release_time: 31622399u64,
+            tranches: None,
         },
     );
     rule_configs_map.insert(
@@ -41,10 +42,11 @@ pub fn mock_instantiate_msg(distribute_token: Addr) -> InstantiateMsg {
             rule_total_amount: 200_000_000_000_000u128,
             start_release_amount: 0u128,
             lock_start_time: 1688366468u64,
-            lock_end_time: 1704264068u64,
+            lock_end_time: 0,
             start_linear_release_time: 1704264069u64,
             unlock_linear_release_amount: 200_000_000_000_000u128,
             unlock_linear_release_time: 157852799u64,
+            tranches: None,
         },
     );
 
@@ -56,10 +58,11 @@ pub fn mock_instantiate_msg(distribute_token: Addr) -> InstantiateMsg {
             rule_total_amount: 230_000_000_000_000u128,
             start_release_amount: 69_000_000_000_000u128,
             lock_start_time: 1688366468u64,
-            lock_end_time: 1704264068u64,
+            lock_end_time: 0,
             start_linear_release_time: 1704264069u64,
             unlock_linear_release_amount: 161_000_000_000_000u128,
             unlock_linear_release_time: 94694400u64,
+            tranches: None,
         },
     );
     rule_configs_map.insert(
@@ -70,10 +73,11 @@ pub fn mock_instantiate_msg(distribute_token: Addr) -> InstantiateMsg {
             rule_total_amount: 350_000_000_000_000u128,
             start_release_amount: 0u128,
             lock_start_time: 1688366468u64,
-            lock_end_time: 1704264068u64,
+            lock_end_time: 0,
             start_linear_release_time: 1704264069u64,
             unlock_linear_release_amount: 350_000_000_000_000u128,
             unlock_linear_release_time: 65836800u64,
+            tranches: None,
         },
     );
 
@@ -85,10 +89,11 @@ pub fn mock_instantiate_msg(distribute_token: Addr) -> InstantiateMsg {
             rule_total_amount: 45_000_000_000_000u128,
             start_release_amount: 0u128,
             lock_start_time: 1688366468u64,
-            lock_end_time: 1704264068u64,
+            lock_end_time: 0,
             start_linear_release_time: 1704264069u64,
             unlock_linear_release_amount: 45_000_000_000_000u128,
             unlock_linear_release_time: 94694400u64,
+            tranches: None,
         },
     );
 
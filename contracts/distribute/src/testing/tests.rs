@@ -1,10 +1,13 @@
+use crate::contract::migrate;
 use crate::error::ContractError;
-use crate::handler::{add_rule_config, update_config, update_rule_config};
-use crate::msg::{RuleConfigMsg, UpdateRuleConfigMsg};
-use crate::querier::{query_config, query_rule_info};
+use crate::handler::{add_rule_config, claim, update_config, update_rule_config};
+use crate::msg::{MigrateMsg, RuleConfigMsg, UpdateRuleConfigMsg};
+use crate::querier::{query_claimable_info, query_config, query_rule_info};
+use crate::state::{LegacyRuleConfig, RuleConfigState, Tranche};
 use crate::testing::mock_fn::{mock_instantiate, mock_instantiate_msg};
-use cosmwasm_std::testing::mock_info;
-use cosmwasm_std::{Addr, StdError};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{Addr, Env, StdError, Storage, Timestamp};
+use cw2::{get_contract_version, set_contract_version};
 
 const SEILOR_TOKEN: &str = "seilor_token";
 
@@ -93,6 +96,7 @@ fn test_add_rule_config() {
             start_linear_release_time: 0,
             unlock_linear_release_amount: 0,
             unlock_linear_release_time: 1,
+            tranches: None,
         },
     );
 
@@ -124,6 +128,7 @@ fn test_add_rule_config() {
             start_linear_release_time: 0,
             unlock_linear_release_amount: 0,
             unlock_linear_release_time: 1,
+            tranches: None,
         },
     );
     assert!(res.is_ok());
@@ -139,6 +144,56 @@ fn test_add_rule_config() {
     let config = query_config(deps.as_ref()).unwrap();
     assert_eq!(config.rules_total_amount, 800000000000200u128);
 
+    // releases above the rule total
+    let res = add_rule_config(
+        deps.as_mut(),
+        info.clone(),
+        "test".to_string(),
+        RuleConfigMsg {
+            rule_name: "test".to_string(),
+            rule_owner: Addr::unchecked("test_owner"),
+            rule_total_amount: 100,
+            start_release_amount: 50,
+            lock_start_time: 1688366468u64,
+            lock_end_time: 0,
+            start_linear_release_time: 1688366468u64,
+            unlock_linear_release_amount: 60,
+            unlock_linear_release_time: 1719988868,
+            tranches: None,
+        },
+    );
+    assert_eq!(
+        res.err().unwrap(),
+        ContractError::Std(StdError::generic_err(
+            "release amounts must not exceed rule total amount",
+        ))
+    );
+
+    // lock_end_time is deprecated
+    let res = add_rule_config(
+        deps.as_mut(),
+        info.clone(),
+        "test".to_string(),
+        RuleConfigMsg {
+            rule_name: "test".to_string(),
+            rule_owner: Addr::unchecked("test_owner"),
+            rule_total_amount: 100,
+            start_release_amount: 50,
+            lock_start_time: 1688366468u64,
+            lock_end_time: 1719988868u64,
+            start_linear_release_time: 1688366468u64,
+            unlock_linear_release_amount: 50,
+            unlock_linear_release_time: 1719988868,
+            tranches: None,
+        },
+    );
+    assert_eq!(
+        res.err().unwrap(),
+        ContractError::Std(StdError::generic_err(
+            "lock_end_time is deprecated and must be 0",
+        ))
+    );
+
     let msg = mock_instantiate_msg(Addr::unchecked(SEILOR_TOKEN));
     let (mut deps, _, info, res) = mock_instantiate(msg.clone());
     assert!(res.is_ok());
@@ -158,6 +213,7 @@ fn test_add_rule_config() {
             start_linear_release_time: 0,
             unlock_linear_release_amount: 0,
             unlock_linear_release_time: 1,
+            tranches: None,
         },
     );
     assert!(res.is_err());
@@ -180,6 +236,7 @@ fn test_add_rule_config() {
             start_linear_release_time: 0,
             unlock_linear_release_amount: 0,
             unlock_linear_release_time: 1,
+            tranches: None,
         },
     );
     assert!(res.is_err());
@@ -201,10 +258,11 @@ fn test_add_rule_config() {
             rule_total_amount: 10000000000000u128,
             start_release_amount: 0u128,
             lock_start_time: 1688366468u64,
-            lock_end_time: 1688366468u64,
+            lock_end_time: 0,
             start_linear_release_time: 1688366468u64,
             unlock_linear_release_amount: 10000000000000u128,
             unlock_linear_release_time: 1719988868,
+            tranches: None,
         },
     );
     assert!(res.is_err());
@@ -215,3 +273,281 @@ fn test_add_rule_config() {
         ))
     );
 }
+
+fn tranches_rule_msg(rule_total_amount: u128, tranches: Vec<Tranche>) -> RuleConfigMsg {
+    RuleConfigMsg {
+        rule_name: "tranches".to_string(),
+        rule_owner: Addr::unchecked("rule_owner"),
+        rule_total_amount,
+        start_release_amount: 0,
+        lock_start_time: 1000,
+        lock_end_time: 0,
+        start_linear_release_time: 0,
+        unlock_linear_release_amount: 0,
+        unlock_linear_release_time: 0,
+        tranches: Some(tranches),
+    }
+}
+
+#[test]
+fn test_tranches() {
+    let mut msg = mock_instantiate_msg(Addr::unchecked(SEILOR_TOKEN));
+    msg.total_amount += 4000;
+    let (mut deps, _, info, res) = mock_instantiate(msg.clone());
+    assert!(res.is_ok());
+
+    // cliff at 1000, linear 2000..3000, cliff at 2500, linear 4000..5000
+    let tranches = vec![
+        Tranche::Cliff {
+            release_time: 1000,
+            amount: 100,
+        },
+        Tranche::Linear {
+            start_time: 2000,
+            end_time: 3000,
+            amount: 1000,
+        },
+        Tranche::Cliff {
+            release_time: 2500,
+            amount: 200,
+        },
+        Tranche::Linear {
+            start_time: 4000,
+            end_time: 5000,
+            amount: 700,
+        },
+    ];
+    let res = add_rule_config(
+        deps.as_mut(),
+        info.clone(),
+        "tranches".to_string(),
+        tranches_rule_msg(2000, tranches.clone()),
+    );
+    assert!(res.is_ok());
+    let query_data = query_rule_info(deps.as_ref(), "tranches".to_string()).unwrap();
+    assert_eq!(query_data.rule_config.tranches, tranches);
+
+    let at = |seconds: u64| -> Env {
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(seconds);
+        env
+    };
+    let rule_owner = mock_info("rule_owner", &[]);
+
+    let res = query_claimable_info(deps.as_ref(), at(999), "tranches".to_string()).unwrap();
+    assert_eq!(res.can_claim_amount, 0);
+
+    let res = query_claimable_info(deps.as_ref(), at(1500), "tranches".to_string()).unwrap();
+    assert_eq!(res.can_claim_amount, 100);
+    assert_eq!(res.release_amount, 100);
+    assert_eq!(res.linear_release_amount, 0);
+
+    let res = query_claimable_info(deps.as_ref(), at(2600), "tranches".to_string()).unwrap();
+    assert_eq!(res.release_amount, 300);
+    assert_eq!(res.linear_release_amount, 600);
+    assert_eq!(res.can_claim_amount, 900);
+
+    let _res = claim(
+        deps.as_mut(),
+        at(2600),
+        rule_owner.clone(),
+        "tranches".to_string(),
+        None,
+    )
+    .unwrap();
+    let query_data = query_rule_info(deps.as_ref(), "tranches".to_string()).unwrap();
+    assert_eq!(query_data.rule_config_state.claimed_amount, 900);
+
+    // the gap between the first linear end and the second linear start
+    let res = query_claimable_info(deps.as_ref(), at(3500), "tranches".to_string()).unwrap();
+    assert_eq!(res.can_claim_amount, 400);
+
+    let res = query_claimable_info(deps.as_ref(), at(4500), "tranches".to_string()).unwrap();
+    assert_eq!(res.linear_release_amount, 1350);
+    assert_eq!(res.can_claim_amount, 750);
+
+    let _res = claim(
+        deps.as_mut(),
+        at(6000),
+        rule_owner.clone(),
+        "tranches".to_string(),
+        None,
+    )
+    .unwrap();
+    let query_data = query_rule_info(deps.as_ref(), "tranches".to_string()).unwrap();
+    assert_eq!(query_data.rule_config_state.claimed_amount, 2000);
+
+    let res = claim(
+        deps.as_mut(),
+        at(7000),
+        rule_owner,
+        "tranches".to_string(),
+        None,
+    );
+    assert_eq!(res.err().unwrap(), ContractError::NoMoreAmountClaim {});
+
+    // amounts must add up to the rule total amount
+    let res = add_rule_config(
+        deps.as_mut(),
+        info.clone(),
+        "bad_total".to_string(),
+        tranches_rule_msg(1999, tranches),
+    );
+    assert_eq!(
+        res.err().unwrap(),
+        ContractError::Std(StdError::generic_err(
+            "tranche amounts must add up to rule total amount",
+        ))
+    );
+
+    let res = add_rule_config(
+        deps.as_mut(),
+        info.clone(),
+        "bad_order".to_string(),
+        tranches_rule_msg(
+            300,
+            vec![
+                Tranche::Cliff {
+                    release_time: 2000,
+                    amount: 100,
+                },
+                Tranche::Cliff {
+                    release_time: 1000,
+                    amount: 200,
+                },
+            ],
+        ),
+    );
+    assert_eq!(
+        res.err().unwrap(),
+        ContractError::Std(StdError::generic_err(
+            "tranches must be ordered by start time",
+        ))
+    );
+
+    let res = add_rule_config(
+        deps.as_mut(),
+        info.clone(),
+        "bad_linear".to_string(),
+        tranches_rule_msg(
+            100,
+            vec![Tranche::Linear {
+                start_time: 2000,
+                end_time: 2000,
+                amount: 100,
+            }],
+        ),
+    );
+    assert_eq!(
+        res.err().unwrap(),
+        ContractError::Std(StdError::generic_err(
+            "linear tranche must end after it starts",
+        ))
+    );
+
+    let res = add_rule_config(
+        deps.as_mut(),
+        info.clone(),
+        "bad_empty".to_string(),
+        tranches_rule_msg(100, vec![]),
+    );
+    assert_eq!(
+        res.err().unwrap(),
+        ContractError::Std(StdError::generic_err("tranches must not be empty"))
+    );
+
+    let mut rule_msg = tranches_rule_msg(
+        100,
+        vec![Tranche::Cliff {
+            release_time: 1000,
+            amount: 100,
+        }],
+    );
+    rule_msg.start_release_amount = 100;
+    let res = add_rule_config(deps.as_mut(), info, "bad_mixed".to_string(), rule_msg);
+    assert_eq!(
+        res.err().unwrap(),
+        ContractError::Std(StdError::generic_err(
+            "tranches can't be combined with the single segment release",
+        ))
+    );
+}
+
+#[test]
+fn test_migrate() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+
+    // rule and state as stored by 0.1.0, before tranches
+    set_contract_version(
+        deps.as_mut().storage,
+        "kryptonite.finance:seilor-distribute",
+        "0.1.0",
+    )
+    .unwrap();
+    let legacy_rule_config = LegacyRuleConfig {
+        rule_name: "legacy".to_string(),
+        rule_owner: Addr::unchecked("rule_owner"),
+        rule_total_amount: 1000,
+        start_release_amount: 200,
+        lock_start_time: 1000,
+        lock_end_time: 2000,
+        start_linear_release_time: 1500,
+        end_linear_release_time: 3000,
+        unlock_linear_release_amount: 800,
+        unlock_linear_release_time: 1500,
+        linear_release_per_second: 533333333333333,
+    };
+    cw_storage_plus::Map::<&str, LegacyRuleConfig>::new("rule_config")
+        .save(deps.as_mut().storage, "legacy", &legacy_rule_config)
+        .unwrap();
+    deps.storage.set(
+        &cw_storage_plus::Map::<&str, RuleConfigState>::new("rule_config_state")
+            .key("legacy"),
+        br#"{"is_start_release":true,"claimed_amount":"300","released_amount":"200","last_claim_linear_release_time":2200}"#,
+    );
+    assert!(query_rule_info(deps.as_ref(), "legacy".to_string()).is_err());
+
+    let res = migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+    assert_eq!(res.attributes.len(), 2);
+    assert_eq!(res.attributes[1].value, "legacy");
+    assert_eq!(
+        get_contract_version(deps.as_ref().storage).unwrap().version,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    let query_data = query_rule_info(deps.as_ref(), "legacy".to_string()).unwrap();
+    assert_eq!(query_data.rule_config.rule_total_amount, 1000);
+    assert_eq!(query_data.rule_config.lock_start_time, 1000);
+    assert_eq!(
+        query_data.rule_config.tranches,
+        vec![
+            Tranche::Cliff {
+                release_time: 1000,
+                amount: 200,
+            },
+            Tranche::Linear {
+                start_time: 1500,
+                end_time: 3000,
+                amount: 800,
+            },
+        ]
+    );
+    assert_eq!(query_data.rule_config_state.claimed_amount, 300);
+
+    let mut at = env.clone();
+    at.block.time = Timestamp::from_seconds(2250);
+    let res = query_claimable_info(deps.as_ref(), at, "legacy".to_string()).unwrap();
+    assert_eq!(res.release_amount, 200);
+    assert_eq!(res.linear_release_amount, 400);
+    assert_eq!(res.can_claim_amount, 300);
+
+    // migrated rules are left alone
+    let query_data = query_rule_info(deps.as_ref(), "legacy".to_string()).unwrap();
+    let res = migrate(deps.as_mut(), env, MigrateMsg {}).unwrap();
+    assert_eq!(res.attributes.len(), 1);
+    assert_eq!(
+        query_rule_info(deps.as_ref(), "legacy".to_string()).unwrap(),
+        query_data
+    );
+}
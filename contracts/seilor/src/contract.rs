@@ -1,17 +1,46 @@
-use crate::handler::{mint, update_config};
+use crate::error::ContractError;
+use crate::handler::{
+    after_balance_change, approve_mint, assert_launch_limits, assert_no_periodic_allowance,
+    assert_not_frozen, cancel_locked, cancel_stream, checkpoint_balances, claim_locked,
+    claim_mint_escrow, collect_transfer_tax, create_stream, create_vesting, create_wrap_denom,
+    decrease_periodic_allowance, delegate, emit, freeze, increase_periodic_allowance,
+    lift_launch_limits, lower_max_supply, mint, mint_batch, mint_hook_failed, permit, record_burn,
+    record_payment, recover_tokens, reject_mint, remove_minter, restore_allowance, seed_holders,
+    set_emission_schedule, set_launch_limits, set_limit_exempt, set_mint_approval, set_minter,
+    set_supply_excluded, set_tax_exempt, spend_periodic_allowance, transfer_batch, transfer_locked,
+    transfer_tax_attrs, unfreeze, unwrap, update_config, update_transfer_tax, withdraw_stream,
+    wrap,
+};
+use crate::helper::is_empty_str;
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::querier::query_seilor_config;
-use crate::state::{store_seilor_config, SeilorConfig};
+use crate::querier::{
+    query_balance_at, query_burn_history, query_burn_stats, query_circulating_supply,
+    query_delegation, query_emission_schedule, query_escrow, query_escrows_by_recipient,
+    query_escrows_by_sender, query_frozen_accounts, query_launch_limits,
+    query_limit_exempt_accounts, query_mint_approval, query_mint_escrow, query_mint_history,
+    query_mint_hook_failures, query_mint_proposal, query_mint_proposals, query_minters,
+    query_payments_received, query_periodic_allowance, query_permit_nonce, query_projected_supply,
+    query_seilor_config, query_simulate_transfer, query_stream, query_streams_by_recipient,
+    query_streams_by_sender, query_supply_excluded_accounts, query_tax_exempt_accounts,
+    query_top_holders, query_total_supply_at, query_transfer_tax, query_vesting_balance,
+    query_votes, query_votes_at, query_wrapped_supply,
+};
+use crate::state::{
+    is_holders_index_empty, read_holder_seed, read_legacy_seilor_config, read_minter_info,
+    read_seilor_config, read_total_supply_snapshot, store_holder_seed, store_minter_info,
+    store_seilor_config, store_total_supply_snapshot, HolderSeed, MinterInfo, SeilorConfig,
+};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+    attr, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError,
+    StdResult, SubMsgResult, Uint128,
 };
 use cw2::set_contract_version;
 use cw20::MinterResponse;
 use cw20_base::allowances::{
-    execute_burn_from, execute_decrease_allowance, execute_increase_allowance, execute_send_from,
-    execute_transfer_from, query_allowance,
+    deduct_allowance, execute_burn_from, execute_decrease_allowance, execute_increase_allowance,
+    execute_send_from, execute_transfer_from, query_allowance,
 };
 use cw20_base::contract::{execute_burn, instantiate as cw20_instantiate};
 use cw20_base::contract::{
@@ -21,7 +50,6 @@ use cw20_base::contract::{
 };
 use cw20_base::enumerable::{query_all_accounts, query_owner_allowances, query_spender_allowances};
 use cw20_base::msg::{InstantiateMarketingInfo, InstantiateMsg as Cw20InstantiateMsg};
-use cw20_base::ContractError;
 
 // version info for migration info
 const CONTRACT_NAME: &str = "kryptonite.finance:cw20-seilor";
@@ -52,16 +80,25 @@ pub fn instantiate(
         });
     }
 
-    let ins_res = cw20_instantiate(deps.branch(), env, info, cw20_instantiate_msg);
+    let initial_holders = cw20_instantiate_msg
+        .initial_balances
+        .iter()
+        .map(|coin| deps.api.addr_validate(&coin.address))
+        .collect::<StdResult<Vec<Addr>>>()?;
+
+    let ins_res = cw20_instantiate(deps.branch(), env.clone(), info, cw20_instantiate_msg);
     if let Err(err) = ins_res {
         return Err(ContractError::Std(StdError::generic_err(err.to_string())));
     }
 
+    checkpoint_balances(deps.storage, env.block.height, &initial_holders)?;
+
     let seilor_config = SeilorConfig {
         max_supply: msg.max_supply,
         fund: Addr::unchecked(""),
         gov,
-        distribute: Addr::unchecked(""),
+        refresh_reward_enabled: false,
+        mint_to_frozen_enabled: false,
     };
 
     store_seilor_config(deps.storage, &seilor_config)?;
@@ -73,17 +110,91 @@ pub fn instantiate(
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
+        ExecuteMsg::CreateWrapDenom { subdenom } => create_wrap_denom(deps, env, info, subdenom),
+        ExecuteMsg::Wrap { amount } => wrap(deps, env, info, amount),
+        ExecuteMsg::Unwrap {} => unwrap(deps, env, info),
         ExecuteMsg::UpdateConfig {
             fund,
             gov,
-            distribute,
-        } => update_config(deps, info, fund, gov, distribute),
+            refresh_reward_enabled,
+            mint_to_frozen_enabled,
+        } => update_config(
+            deps,
+            info,
+            fund,
+            gov,
+            refresh_reward_enabled,
+            mint_to_frozen_enabled,
+        ),
+        ExecuteMsg::SetMinter { minter, quota } => set_minter(deps, info, minter, quota),
+        ExecuteMsg::RemoveMinter { minter } => remove_minter(deps, info, minter),
+        ExecuteMsg::LowerMaxSupply { max_supply } => lower_max_supply(deps, info, max_supply),
+        ExecuteMsg::Delegate { delegatee } => delegate(deps, env, info, delegatee),
+        ExecuteMsg::SeedHolders { limit } => seed_holders(deps, env, info, limit),
+        ExecuteMsg::Freeze { account } => freeze(deps, info, account),
+        ExecuteMsg::Unfreeze { account } => unfreeze(deps, info, account),
+        ExecuteMsg::UpdateTransferTax {
+            tax_bps,
+            burn_bps,
+            receiver,
+        } => update_transfer_tax(deps, info, tax_bps, burn_bps, receiver),
+        ExecuteMsg::SetTaxExempt { account, exempt } => set_tax_exempt(deps, info, account, exempt),
+        ExecuteMsg::SetLaunchLimits {
+            max_transfer_amount,
+            max_wallet_balance,
+            transfer_cooldown_seconds,
+        } => set_launch_limits(
+            deps,
+            info,
+            max_transfer_amount,
+            max_wallet_balance,
+            transfer_cooldown_seconds,
+        ),
+        ExecuteMsg::LiftLaunchLimits {} => lift_launch_limits(deps, info),
+        ExecuteMsg::SetLimitExempt { account, exempt } => {
+            set_limit_exempt(deps, info, account, exempt)
+        }
+        ExecuteMsg::TransferLocked {
+            recipient,
+            amount,
+            release_at,
+            cancelable,
+        } => transfer_locked(deps, env, info, recipient, amount, release_at, cancelable),
+        ExecuteMsg::ClaimLocked { id } => claim_locked(deps, env, info, id),
+        ExecuteMsg::CancelLocked { id } => cancel_locked(deps, env, info, id),
+        ExecuteMsg::CreateStream {
+            recipient,
+            amount,
+            start,
+            end,
+        } => create_stream(deps, env, info, recipient, amount, start, end),
+        ExecuteMsg::WithdrawStream { id } => withdraw_stream(deps, env, info, id),
+        ExecuteMsg::CancelStream { id } => cancel_stream(deps, env, info, id),
+        ExecuteMsg::RecoverTokens {
+            asset,
+            recipient,
+            amount,
+        } => recover_tokens(deps, env, info, asset, recipient, amount),
+        ExecuteMsg::SetSupplyExcluded { account, excluded } => {
+            set_supply_excluded(deps, info, account, excluded)
+        }
+        ExecuteMsg::Permit {
+            owner,
+            spender,
+            amount,
+            expires,
+            nonce,
+            signature,
+            pubkey,
+        } => permit(
+            deps, env, owner, spender, amount, expires, nonce, signature, pubkey,
+        ),
         ExecuteMsg::Mint {
             recipient,
             amount,
@@ -93,62 +204,388 @@ pub fn execute(
             let recipient = deps.api.addr_validate(&recipient)?;
             mint(deps, env, info, recipient, amount, contract, msg)
         }
+        ExecuteMsg::MintBatch { mints } => mint_batch(deps, env, info, mints),
+        ExecuteMsg::SetMintApproval {
+            threshold,
+            window_seconds,
+        } => set_mint_approval(deps, info, threshold, window_seconds),
+        ExecuteMsg::ApproveMint { id } => approve_mint(deps, env, info, id),
+        ExecuteMsg::RejectMint { id } => reject_mint(deps, env, info, id),
+        ExecuteMsg::ClaimMintEscrow {} => claim_mint_escrow(deps, env, info),
+        ExecuteMsg::SetEmissionSchedule { curve, receivers } => {
+            set_emission_schedule(deps, env, info, curve, receivers)
+        }
+        ExecuteMsg::Emit {} => emit(deps, env),
+        ExecuteMsg::CreateVesting {
+            recipient,
+            amount,
+            schedule,
+        } => create_vesting(deps, env, info, recipient, amount, schedule),
 
         // we override these from cw20
-        ExecuteMsg::Burn { amount } => {
+        ExecuteMsg::Burn { amount, reason } => {
             // Burn has been modified to directly inherit the standard, and this modification will add gas to the VE module stacking. And complexity.
             // let user = deps.api.addr_validate(&user)?;
             // burn(deps, env, info, user, amount.u128())
-            execute_burn(deps, env, info, amount)
+            assert_not_frozen(deps.storage, &info.sender)?;
+            let users = vec![info.sender.clone()];
+            let res = execute_burn(deps.branch(), env.clone(), info, amount)?;
+            let burn_attrs = record_burn(deps.storage, &env, &users[0], amount, reason)?;
+            after_balance_change(deps, env, res.add_attributes(burn_attrs), users)
+        }
+        ExecuteMsg::BurnFrom {
+            owner,
+            amount,
+            reason,
+        } => {
+            let users = vec![deps.api.addr_validate(&owner)?];
+            assert_not_frozen(deps.storage, &info.sender)?;
+            assert_not_frozen(deps.storage, &users[0])?;
+            let parked =
+                spend_periodic_allowance(deps.storage, &env, &users[0], &info.sender, amount)?;
+            let res = execute_burn_from(deps.branch(), env.clone(), info, owner, amount)?;
+            if let Some(parked) = parked {
+                restore_allowance(deps.storage, parked)?;
+            }
+            let burn_attrs = record_burn(deps.storage, &env, &users[0], amount, reason)?;
+            after_balance_change(deps, env, res.add_attributes(burn_attrs), users)
         }
-        ExecuteMsg::BurnFrom { owner, amount } => execute_burn_from(deps, env, info, owner, amount),
         // these all come from cw20-base to implement the cw20 standard
         ExecuteMsg::Transfer { recipient, amount } => {
-            execute_transfer(deps, env, info, recipient, amount)
+            assert_not_frozen(deps.storage, &info.sender)?;
+            let mut users = vec![info.sender.clone(), deps.api.addr_validate(&recipient)?];
+            let tax = collect_transfer_tax(deps.storage, &users[0], &users[1], amount)?;
+            users.extend(tax.receiver.clone());
+            let res =
+                execute_transfer(deps.branch(), env.clone(), info, recipient, tax.net_amount)?
+                    .add_attributes(transfer_tax_attrs(&tax));
+            assert_launch_limits(deps.storage, &env, &users[0], &users[1], amount)?;
+            after_balance_change(deps, env, res, users)
+        }
+        ExecuteMsg::TransferBatch { transfers } => transfer_batch(deps, env, info, transfers),
+        ExecuteMsg::TransferWithReference {
+            recipient,
+            amount,
+            reference,
+        } => {
+            assert_not_frozen(deps.storage, &info.sender)?;
+            let mut users = vec![info.sender.clone(), deps.api.addr_validate(&recipient)?];
+            let tax = collect_transfer_tax(deps.storage, &users[0], &users[1], amount)?;
+            users.extend(tax.receiver.clone());
+            let payment_attrs =
+                record_payment(deps.storage, &env, &users[0], &users[1], &tax, reference)?;
+            let res =
+                execute_transfer(deps.branch(), env.clone(), info, recipient, tax.net_amount)?
+                    .add_attributes(transfer_tax_attrs(&tax))
+                    .add_attributes(payment_attrs);
+            assert_launch_limits(deps.storage, &env, &users[0], &users[1], amount)?;
+            after_balance_change(deps, env, res, users)
+        }
+        ExecuteMsg::SendWithReference {
+            contract,
+            amount,
+            msg,
+            reference,
+        } => {
+            assert_not_frozen(deps.storage, &info.sender)?;
+            let mut users = vec![info.sender.clone(), deps.api.addr_validate(&contract)?];
+            let tax = collect_transfer_tax(deps.storage, &users[0], &users[1], amount)?;
+            users.extend(tax.receiver.clone());
+            let payment_attrs =
+                record_payment(deps.storage, &env, &users[0], &users[1], &tax, reference)?;
+            let res = execute_send(
+                deps.branch(),
+                env.clone(),
+                info,
+                contract,
+                tax.net_amount,
+                msg,
+            )?
+            .add_attributes(transfer_tax_attrs(&tax))
+            .add_attributes(payment_attrs);
+            assert_launch_limits(deps.storage, &env, &users[0], &users[1], amount)?;
+            after_balance_change(deps, env, res, users)
         }
         ExecuteMsg::Send {
             contract,
             amount,
             msg,
-        } => execute_send(deps, env, info, contract, amount, msg),
+        } => {
+            assert_not_frozen(deps.storage, &info.sender)?;
+            let mut users = vec![info.sender.clone(), deps.api.addr_validate(&contract)?];
+            let tax = collect_transfer_tax(deps.storage, &users[0], &users[1], amount)?;
+            users.extend(tax.receiver.clone());
+            let res = execute_send(
+                deps.branch(),
+                env.clone(),
+                info,
+                contract,
+                tax.net_amount,
+                msg,
+            )?
+            .add_attributes(transfer_tax_attrs(&tax));
+            assert_launch_limits(deps.storage, &env, &users[0], &users[1], amount)?;
+            after_balance_change(deps, env, res, users)
+        }
         ExecuteMsg::IncreaseAllowance {
             spender,
             amount,
             expires,
-        } => execute_increase_allowance(deps, env, info, spender, amount, expires),
+        } => {
+            let spender_addr = deps.api.addr_validate(&spender)?;
+            assert_no_periodic_allowance(deps.storage, &info.sender, &spender_addr)?;
+            Ok(execute_increase_allowance(
+                deps, env, info, spender, amount, expires,
+            )?)
+        }
         ExecuteMsg::DecreaseAllowance {
             spender,
             amount,
             expires,
-        } => execute_decrease_allowance(deps, env, info, spender, amount, expires),
+        } => Ok(execute_decrease_allowance(
+            deps, env, info, spender, amount, expires,
+        )?),
+        ExecuteMsg::IncreasePeriodicAllowance {
+            spender,
+            amount_per_period,
+            period_seconds,
+            expires,
+        } => increase_periodic_allowance(
+            deps,
+            env,
+            info,
+            spender,
+            amount_per_period,
+            period_seconds,
+            expires,
+        ),
+        ExecuteMsg::DecreasePeriodicAllowance {
+            spender,
+            amount_per_period,
+        } => decrease_periodic_allowance(deps, info, spender, amount_per_period),
         ExecuteMsg::TransferFrom {
             owner,
             recipient,
             amount,
-        } => execute_transfer_from(deps, env, info, owner, recipient, amount),
+        } => {
+            let mut users = vec![
+                deps.api.addr_validate(&owner)?,
+                deps.api.addr_validate(&recipient)?,
+            ];
+            assert_not_frozen(deps.storage, &info.sender)?;
+            assert_not_frozen(deps.storage, &users[0])?;
+            let parked =
+                spend_periodic_allowance(deps.storage, &env, &users[0], &info.sender, amount)?;
+            let tax = collect_transfer_tax(deps.storage, &users[0], &users[1], amount)?;
+            if !tax.tax_amount.is_zero() {
+                deduct_allowance(
+                    deps.storage,
+                    &users[0],
+                    &info.sender,
+                    &env.block,
+                    tax.tax_amount,
+                )?;
+            }
+            users.extend(tax.receiver.clone());
+            let res = execute_transfer_from(
+                deps.branch(),
+                env.clone(),
+                info,
+                owner,
+                recipient,
+                tax.net_amount,
+            )?
+            .add_attributes(transfer_tax_attrs(&tax));
+            if let Some(parked) = parked {
+                restore_allowance(deps.storage, parked)?;
+            }
+            assert_launch_limits(deps.storage, &env, &users[0], &users[1], amount)?;
+            after_balance_change(deps, env, res, users)
+        }
         ExecuteMsg::SendFrom {
             owner,
             contract,
             amount,
             msg,
-        } => execute_send_from(deps, env, info, owner, contract, amount, msg),
+        } => {
+            let mut users = vec![
+                deps.api.addr_validate(&owner)?,
+                deps.api.addr_validate(&contract)?,
+            ];
+            assert_not_frozen(deps.storage, &info.sender)?;
+            assert_not_frozen(deps.storage, &users[0])?;
+            let parked =
+                spend_periodic_allowance(deps.storage, &env, &users[0], &info.sender, amount)?;
+            let tax = collect_transfer_tax(deps.storage, &users[0], &users[1], amount)?;
+            if !tax.tax_amount.is_zero() {
+                deduct_allowance(
+                    deps.storage,
+                    &users[0],
+                    &info.sender,
+                    &env.block,
+                    tax.tax_amount,
+                )?;
+            }
+            users.extend(tax.receiver.clone());
+            let res = execute_send_from(
+                deps.branch(),
+                env.clone(),
+                info,
+                owner,
+                contract,
+                tax.net_amount,
+                msg,
+            )?
+            .add_attributes(transfer_tax_attrs(&tax));
+            if let Some(parked) = parked {
+                restore_allowance(deps.storage, parked)?;
+            }
+            assert_launch_limits(deps.storage, &env, &users[0], &users[1], amount)?;
+            after_balance_change(deps, env, res, users)
+        }
         ExecuteMsg::UpdateMarketing {
             project,
             description,
             marketing,
-        } => execute_update_marketing(deps, env, info, project, description, marketing),
-        ExecuteMsg::UploadLogo(logo) => execute_upload_logo(deps, env, info, logo),
+        } => Ok(execute_update_marketing(
+            deps,
+            env,
+            info,
+            project,
+            description,
+            marketing,
+        )?),
+        ExecuteMsg::UploadLogo(logo) => Ok(execute_upload_logo(deps, env, info, logo)?),
         ExecuteMsg::UpdateMinter { new_minter } => {
-            execute_update_minter(deps, env, info, new_minter)
+            Ok(execute_update_minter(deps, env, info, new_minter)?)
         }
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         // custom queries
         QueryMsg::SeilorConfig {} => to_binary(&query_seilor_config(deps)?),
+        QueryMsg::Minters { start_after, limit } => {
+            to_binary(&query_minters(deps, start_after, limit)?)
+        }
+        QueryMsg::BalanceAt { address, height } => {
+            to_binary(&query_balance_at(deps, address, height)?)
+        }
+        QueryMsg::TotalSupplyAt { height } => to_binary(&query_total_supply_at(deps, height)?),
+        QueryMsg::Votes { address } => to_binary(&query_votes(deps, address)?),
+        QueryMsg::VotesAt { address, height } => to_binary(&query_votes_at(deps, address, height)?),
+        QueryMsg::Delegation { delegator } => to_binary(&query_delegation(deps, delegator)?),
+        QueryMsg::FrozenAccounts { start_after, limit } => {
+            to_binary(&query_frozen_accounts(deps, start_after, limit)?)
+        }
+        QueryMsg::TransferTax {} => to_binary(&query_transfer_tax(deps)?),
+        QueryMsg::TaxExemptAccounts { start_after, limit } => {
+            to_binary(&query_tax_exempt_accounts(deps, start_after, limit)?)
+        }
+        QueryMsg::PaymentsReceived {
+            recipient,
+            reference,
+            start_after,
+            limit,
+        } => to_binary(&query_payments_received(
+            deps,
+            recipient,
+            reference,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::MintApproval {} => to_binary(&query_mint_approval(deps)?),
+        QueryMsg::MintProposal { id } => to_binary(&query_mint_proposal(deps, env, id)?),
+        QueryMsg::MintProposals { start_after, limit } => {
+            to_binary(&query_mint_proposals(deps, env, start_after, limit)?)
+        }
+        QueryMsg::LaunchLimits {} => to_binary(&query_launch_limits(deps)?),
+        QueryMsg::LimitExemptAccounts { start_after, limit } => {
+            to_binary(&query_limit_exempt_accounts(deps, start_after, limit)?)
+        }
+        QueryMsg::SimulateTransfer {
+            sender,
+            recipient,
+            amount,
+        } => to_binary(&query_simulate_transfer(deps, sender, recipient, amount)?),
+        QueryMsg::CirculatingSupply {} => to_binary(&query_circulating_supply(deps)?),
+        QueryMsg::WrappedSupply {} => to_binary(&query_wrapped_supply(deps)?),
+        QueryMsg::SupplyExcludedAccounts { start_after, limit } => {
+            to_binary(&query_supply_excluded_accounts(deps, start_after, limit)?)
+        }
+        QueryMsg::TopHolders { start_after, limit } => {
+            to_binary(&query_top_holders(deps, start_after, limit)?)
+        }
+        QueryMsg::Escrow { id } => to_binary(&query_escrow(deps, id)?),
+        QueryMsg::EscrowsBySender {
+            sender,
+            start_after,
+            limit,
+        } => to_binary(&query_escrows_by_sender(deps, sender, start_after, limit)?),
+        QueryMsg::EscrowsByRecipient {
+            recipient,
+            start_after,
+            limit,
+        } => to_binary(&query_escrows_by_recipient(
+            deps,
+            recipient,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::Stream { id } => to_binary(&query_stream(deps, env, id)?),
+        QueryMsg::StreamsBySender {
+            sender,
+            start_after,
+            limit,
+        } => to_binary(&query_streams_by_sender(
+            deps,
+            env,
+            sender,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::StreamsByRecipient {
+            recipient,
+            start_after,
+            limit,
+        } => to_binary(&query_streams_by_recipient(
+            deps,
+            env,
+            recipient,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::MintHistory {
+            minter,
+            start_after,
+            limit,
+        } => to_binary(&query_mint_history(deps, minter, start_after, limit)?),
+        QueryMsg::MintEscrow { address } => to_binary(&query_mint_escrow(deps, address)?),
+        QueryMsg::MintHookFailures {
+            recipient,
+            start_after,
+            limit,
+        } => to_binary(&query_mint_hook_failures(
+            deps,
+            recipient,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::BurnStats { address } => to_binary(&query_burn_stats(deps, address)?),
+        QueryMsg::BurnHistory { start_after, limit } => {
+            to_binary(&query_burn_history(deps, start_after, limit)?)
+        }
+        QueryMsg::EmissionSchedule {} => to_binary(&query_emission_schedule(deps, env)?),
+        QueryMsg::ProjectedSupply { at } => to_binary(&query_projected_supply(deps, env, at)?),
+        QueryMsg::VestingBalance { address } => {
+            to_binary(&query_vesting_balance(deps, env, address)?)
+        }
+        QueryMsg::PeriodicAllowance { owner, spender } => {
+            to_binary(&query_periodic_allowance(deps, env, owner, spender)?)
+        }
+        QueryMsg::PermitNonce { owner } => to_binary(&query_permit_nonce(deps, owner)?),
 
         QueryMsg::Balance { address } => to_binary(&query_balance(deps, address)?),
         QueryMsg::TokenInfo {} => to_binary(&query_token_info(deps)?),
@@ -180,6 +617,71 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    // mint hooks are the only submessages, replied to on error with the mint id as id
+    match msg.result {
+        SubMsgResult::Err(error) => mint_hook_failed(deps, env, msg.id, error),
+        SubMsgResult::Ok(_) => Err(ContractError::Std(StdError::generic_err(
+            "Unexpected reply",
+        ))),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> StdResult<Response> {
+    // move the legacy fund/distribute mint permission into the minter registry, only a config
+    // stored before the registry still has distribute, so removed minters stay removed
+    let legacy_config = read_legacy_seilor_config(deps.storage)?;
+    let mut attrs = vec![attr("action", "migrate")];
+    if let Some(distribute) = legacy_config.distribute {
+        let default_quota = Uint128::from(legacy_config.max_supply);
+        let legacy_minters = vec![
+            (legacy_config.fund.clone(), msg.fund_quota),
+            (distribute, msg.distribute_quota),
+        ];
+        for (minter, quota) in legacy_minters {
+            if is_empty_str(minter.as_str()) || read_minter_info(deps.storage, &minter)?.is_some() {
+                continue;
+            }
+            let minter_info = MinterInfo {
+                quota: quota.unwrap_or(default_quota),
+                minted: Uint128::zero(),
+            };
+            store_minter_info(deps.storage, &minter, &minter_info)?;
+            attrs.push(attr("minter", minter.to_string()));
+        }
+
+        // fields missing from the stored config fall back to their serde defaults
+        let current_config = read_seilor_config(deps.storage)?;
+        let seilor_config = SeilorConfig {
+            max_supply: legacy_config.max_supply,
+            fund: legacy_config.fund,
+            gov: legacy_config.gov,
+            refresh_reward_enabled: legacy_config.refresh_reward_enabled,
+            mint_to_frozen_enabled: current_config.mint_to_frozen_enabled,
+        };
+        store_seilor_config(deps.storage, &seilor_config)?;
+    }
+
+    // the holders from before checkpointing are too many to walk here, gov seeds their balance
+    // snapshots and the holders index in batches with SeedHolders
+    if read_holder_seed(deps.storage)?.is_none() {
+        let checkpoint = read_total_supply_snapshot(deps.storage)?.is_none();
+        if checkpoint || is_holders_index_empty(deps.storage) {
+            if checkpoint {
+                let total_supply = query_token_info(deps.as_ref())?.total_supply;
+                store_total_supply_snapshot(deps.storage, &total_supply, env.block.height)?;
+            }
+            let holder_seed = HolderSeed {
+                checkpoint,
+                start_after: None,
+            };
+            store_holder_seed(deps.storage, &holder_seed)?;
+            attrs.push(attr("holder_seed", "pending"));
+        }
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new().add_attributes(attrs))
 }
@@ -0,0 +1,38 @@
+use cosmwasm_std::{OverflowError, StdError, Uint128};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    OverflowError(#[from] OverflowError),
+
+    #[error("{0}")]
+    Cw20(#[from] cw20_base::ContractError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("AccountFrozen,account:{0}")]
+    AccountFrozen(String),
+
+    #[error("VestingLocked,account:{0}")]
+    VestingLocked(String),
+
+    #[error("MaxTransferExceeded,max:{0}")]
+    MaxTransferExceeded(Uint128),
+
+    #[error("MaxWalletExceeded,account:{0}")]
+    MaxWalletExceeded(String),
+
+    #[error("TransferCooldown,until:{0}")]
+    TransferCooldown(u64),
+
+    #[error("Invalid permit signature")]
+    InvalidPermitSignature {},
+
+    #[error("InvalidPermitNonce,expected:{0}")]
+    InvalidPermitNonce(u64),
+}
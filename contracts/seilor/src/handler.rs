@@ -1,16 +1,61 @@
-use crate::helper::is_empty_str;
+use crate::error::ContractError;
+use crate::helper::{is_empty_str, pubkey_to_address};
 use crate::mint_receiver::Cw20MintReceiveMsg;
-use crate::state::{read_seilor_config, store_seilor_config};
-use cosmwasm_std::{attr, Addr, Binary, DepsMut, Env, MessageInfo, Response, StdError, Uint128};
-use cw20_base::contract::execute_mint;
-use cw20_base::ContractError;
+use crate::msg::{
+    EmissionCurve, EmissionReceiver, PermitMsg, RecoverAsset, SeilorFundMsg, VestingSchedule,
+};
+use crate::state::{
+    append_burn_record, append_mint_proposal, append_mint_record, append_payment_record,
+    is_frozen_account, is_limit_exempt_account, is_tax_exempt_account, next_escrow_id,
+    next_stream_id, read_all_supply_excluded_accounts, read_balance_snapshot, read_burned,
+    read_delegate, read_emission_config, read_escrow, read_holder_seed, read_last_transfer_time,
+    read_launch_limits, read_mint_approval_config, read_mint_escrow, read_mint_proposal,
+    read_mint_record, read_mint_window, read_minter_info, read_periodic_allowance,
+    read_permit_nonce, read_seilor_config, read_stream, read_total_burned,
+    read_transfer_tax_config, read_vesting_account, read_votes, read_wrap_denom,
+    read_wrapped_supply, remove_escrow, remove_frozen_account, remove_holder_balance,
+    remove_holder_seed, remove_limit_exempt_account, remove_mint_escrow, remove_mint_proposal,
+    remove_minter_info, remove_periodic_allowance, remove_stream, remove_supply_excluded_account,
+    remove_tax_exempt_account, store_balance_snapshot, store_burned, store_delegate,
+    store_emission_config, store_escrow, store_frozen_account, store_holder_balance,
+    store_holder_seed, store_last_transfer_time, store_launch_limits, store_limit_exempt_account,
+    store_mint_approval_config, store_mint_escrow, store_mint_hook_failure, store_mint_window,
+    store_minter_info, store_periodic_allowance, store_permit_nonce, store_seilor_config,
+    store_stream, store_supply_excluded_account, store_tax_exempt_account, store_total_burned,
+    store_total_supply_snapshot, store_transfer_tax_config, store_vesting_account, store_votes,
+    store_wrap_denom, store_wrapped_supply, BurnRecord, EmissionConfig, Escrow, LaunchLimits,
+    MintApprovalConfig, MintHookFailure, MintProposal, MintRecord, MintWindow, MinterInfo,
+    PaymentRecord, PeriodicAllowance, Stream, VestingAccount,
+};
+use crate::token_factory::{into_custom_response, token_factory_denom, TokenFactory};
+use cosmwasm_std::{
+    attr, coin, coins, to_binary, to_vec, Addr, Attribute, BankMsg, Binary, CosmosMsg, DepsMut,
+    Env, MessageInfo, Order, Response, StdError, StdResult, Storage, SubMsg, Uint128, WasmMsg,
+};
+use cw20::{AllowanceResponse, Cw20ExecuteMsg};
+use cw20_base::allowances::execute_increase_allowance;
+use cw20_base::contract::{execute_mint, execute_transfer};
+use cw20_base::state::{ALLOWANCES, ALLOWANCES_SPENDER, BALANCES, TOKEN_INFO};
+use cw_storage_plus::Bound;
+use cw_utils::{must_pay, Expiration};
+use sha2::{Digest, Sha256};
+
+const BPS_DENOMINATOR: u64 = 10_000;
+
+const MAX_BURN_REASON_LEN: usize = 128;
+
+const MAX_PAYMENT_REFERENCE_LEN: usize = 128;
+
+// the circulating supply query reads the balance of every excluded account
+const MAX_SUPPLY_EXCLUDED_ACCOUNTS: usize = 30;
 
 pub fn update_config(
     deps: DepsMut,
     info: MessageInfo,
     fund: Option<Addr>,
     gov: Option<Addr>,
-    distribute: Option<Addr>,
+    refresh_reward_enabled: Option<bool>,
+    mint_to_frozen_enabled: Option<bool>,
 ) -> Result<Response, ContractError> {
     let mut seilor_config = read_seilor_config(deps.storage)?;
 
@@ -33,9 +78,19 @@ pub fn update_config(
         seilor_config.gov = gov.clone();
         attrs.push(attr("gov", gov.to_string()));
     }
-    if let Some(distribute) = distribute {
-        seilor_config.distribute = distribute.clone();
-        attrs.push(attr("distribute", distribute.to_string()));
+    if let Some(refresh_reward_enabled) = refresh_reward_enabled {
+        seilor_config.refresh_reward_enabled = refresh_reward_enabled;
+        attrs.push(attr(
+            "refresh_reward_enabled",
+            refresh_reward_enabled.to_string(),
+        ));
+    }
+    if let Some(mint_to_frozen_enabled) = mint_to_frozen_enabled {
+        seilor_config.mint_to_frozen_enabled = mint_to_frozen_enabled;
+        attrs.push(attr(
+            "mint_to_frozen_enabled",
+            mint_to_frozen_enabled.to_string(),
+        ));
     }
 
     store_seilor_config(deps.storage, &seilor_config)?;
@@ -43,64 +98,2242 @@ pub fn update_config(
     Ok(Response::new().add_attributes(attrs))
 }
 
-pub fn mint(
-    mut deps: DepsMut,
-    env: Env,
+// lowers SeilorConfig.max_supply together with the cw20 minter cap, never below the current supply
+pub fn lower_max_supply(
+    deps: DepsMut,
     info: MessageInfo,
-    user: Addr,
-    amount: Uint128,
-    contract: Option<String>,
-    msg: Option<Binary>,
+    max_supply: u128,
+) -> Result<Response, ContractError> {
+    let mut seilor_config = read_seilor_config(deps.storage)?;
+
+    if info.sender != seilor_config.gov {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if max_supply >= seilor_config.max_supply {
+        return Err(ContractError::Std(StdError::generic_err(
+            "max_supply can only be lowered",
+        )));
+    }
+
+    let mut token_info = TOKEN_INFO.load(deps.storage)?;
+    if Uint128::from(max_supply) < token_info.total_supply {
+        return Err(ContractError::Std(StdError::generic_err(
+            "max_supply below current supply",
+        )));
+    }
+
+    seilor_config.max_supply = max_supply;
+    store_seilor_config(deps.storage, &seilor_config)?;
+
+    if let Some(mint) = token_info.mint.as_mut() {
+        mint.cap = Some(Uint128::from(max_supply));
+    }
+    TOKEN_INFO.save(deps.storage, &token_info)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "lower_max_supply"),
+        attr("max_supply", max_supply.to_string()),
+    ]))
+}
+
+pub fn set_minter(
+    deps: DepsMut,
+    info: MessageInfo,
+    minter: String,
+    quota: Uint128,
+) -> Result<Response, ContractError> {
+    let seilor_config = read_seilor_config(deps.storage)?;
+    if info.sender != seilor_config.gov {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let minter = deps.api.addr_validate(&minter)?;
+    let minter_info = match read_minter_info(deps.storage, &minter)? {
+        Some(minter_info) => MinterInfo {
+            quota,
+            minted: minter_info.minted,
+        },
+        None => MinterInfo {
+            quota,
+            minted: Uint128::zero(),
+        },
+    };
+    store_minter_info(deps.storage, &minter, &minter_info)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_minter"),
+        attr("minter", minter.to_string()),
+        attr("quota", quota.to_string()),
+    ]))
+}
+
+pub fn remove_minter(
+    deps: DepsMut,
+    info: MessageInfo,
+    minter: String,
 ) -> Result<Response, ContractError> {
-    let msg_sender = info.sender;
     let seilor_config = read_seilor_config(deps.storage)?;
-    let fund = seilor_config.fund;
-    let distribute = seilor_config.distribute;
+    if info.sender != seilor_config.gov {
+        return Err(ContractError::Unauthorized {});
+    }
 
-    if is_empty_str(fund.as_str()) && is_empty_str(distribute.as_str()) {
+    let minter = deps.api.addr_validate(&minter)?;
+    if read_minter_info(deps.storage, &minter)?.is_none() {
         return Err(ContractError::Std(StdError::generic_err(
-            "Fund or distribute contract must to be configured",
+            "Minter not found",
         )));
     }
+    remove_minter_info(deps.storage, &minter);
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "remove_minter"),
+        attr("minter", minter.to_string()),
+    ]))
+}
 
-    if msg_sender.ne(&fund.clone()) && msg_sender.ne(&distribute) {
+pub fn freeze(
+    deps: DepsMut,
+    info: MessageInfo,
+    account: String,
+) -> Result<Response, ContractError> {
+    let seilor_config = read_seilor_config(deps.storage)?;
+    if info.sender != seilor_config.gov {
         return Err(ContractError::Unauthorized {});
     }
 
-    let sub_info = MessageInfo {
-        sender: env.contract.address.clone(),
-        funds: vec![],
+    let account = deps.api.addr_validate(&account)?;
+    store_frozen_account(deps.storage, &account)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "freeze"),
+        attr("account", account.to_string()),
+    ]))
+}
+
+pub fn unfreeze(
+    deps: DepsMut,
+    info: MessageInfo,
+    account: String,
+) -> Result<Response, ContractError> {
+    let seilor_config = read_seilor_config(deps.storage)?;
+    if info.sender != seilor_config.gov {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let account = deps.api.addr_validate(&account)?;
+    if !is_frozen_account(deps.storage, &account) {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Account not frozen",
+        )));
+    }
+    remove_frozen_account(deps.storage, &account);
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "unfreeze"),
+        attr("account", account.to_string()),
+    ]))
+}
+
+pub fn assert_not_frozen(storage: &dyn Storage, account: &Addr) -> Result<(), ContractError> {
+    if is_frozen_account(storage, account) {
+        return Err(ContractError::AccountFrozen(account.to_string()));
+    }
+    Ok(())
+}
+
+pub fn update_transfer_tax(
+    deps: DepsMut,
+    info: MessageInfo,
+    tax_bps: Option<u64>,
+    burn_bps: Option<u64>,
+    receiver: Option<String>,
+) -> Result<Response, ContractError> {
+    let seilor_config = read_seilor_config(deps.storage)?;
+    if info.sender != seilor_config.gov {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut tax_config = read_transfer_tax_config(deps.storage)?;
+    let mut attrs = vec![attr("action", "update_transfer_tax")];
+
+    if let Some(tax_bps) = tax_bps {
+        if tax_bps >= BPS_DENOMINATOR {
+            return Err(ContractError::Std(StdError::generic_err("Invalid tax_bps")));
+        }
+        tax_config.tax_bps = tax_bps;
+        attrs.push(attr("tax_bps", tax_bps.to_string()));
+    }
+    if let Some(burn_bps) = burn_bps {
+        if burn_bps > BPS_DENOMINATOR {
+            return Err(ContractError::Std(StdError::generic_err(
+                "Invalid burn_bps",
+            )));
+        }
+        tax_config.burn_bps = burn_bps;
+        attrs.push(attr("burn_bps", burn_bps.to_string()));
+    }
+    if let Some(receiver) = receiver {
+        if !is_empty_str(&receiver) {
+            deps.api.addr_validate(&receiver)?;
+        }
+        tax_config.receiver = Addr::unchecked(receiver.trim());
+        attrs.push(attr("receiver", receiver));
+    }
+
+    store_transfer_tax_config(deps.storage, &tax_config)?;
+
+    Ok(Response::new().add_attributes(attrs))
+}
+
+pub fn set_tax_exempt(
+    deps: DepsMut,
+    info: MessageInfo,
+    account: String,
+    exempt: bool,
+) -> Result<Response, ContractError> {
+    let seilor_config = read_seilor_config(deps.storage)?;
+    if info.sender != seilor_config.gov {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let account = deps.api.addr_validate(&account)?;
+    if exempt {
+        store_tax_exempt_account(deps.storage, &account)?;
+    } else {
+        remove_tax_exempt_account(deps.storage, &account);
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_tax_exempt"),
+        attr("account", account.to_string()),
+        attr("exempt", exempt.to_string()),
+    ]))
+}
+
+pub fn set_launch_limits(
+    deps: DepsMut,
+    info: MessageInfo,
+    max_transfer_amount: Option<Uint128>,
+    max_wallet_balance: Option<Uint128>,
+    transfer_cooldown_seconds: u64,
+) -> Result<Response, ContractError> {
+    let seilor_config = read_seilor_config(deps.storage)?;
+    if info.sender != seilor_config.gov {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if read_launch_limits(deps.storage)?.lifted {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Launch limits lifted",
+        )));
+    }
+
+    let launch_limits = LaunchLimits {
+        max_transfer_amount,
+        max_wallet_balance,
+        transfer_cooldown_seconds,
+        lifted: false,
+    };
+    store_launch_limits(deps.storage, &launch_limits)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_launch_limits"),
+        attr(
+            "transfer_cooldown_seconds",
+            transfer_cooldown_seconds.to_string(),
+        ),
+    ]))
+}
+
+pub fn lift_launch_limits(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let seilor_config = read_seilor_config(deps.storage)?;
+    if info.sender != seilor_config.gov {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let launch_limits = LaunchLimits {
+        lifted: true,
+        ..LaunchLimits::default()
+    };
+    store_launch_limits(deps.storage, &launch_limits)?;
+
+    Ok(Response::new().add_attributes(vec![attr("action", "lift_launch_limits")]))
+}
+
+pub fn set_limit_exempt(
+    deps: DepsMut,
+    info: MessageInfo,
+    account: String,
+    exempt: bool,
+) -> Result<Response, ContractError> {
+    let seilor_config = read_seilor_config(deps.storage)?;
+    if info.sender != seilor_config.gov {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let account = deps.api.addr_validate(&account)?;
+    if exempt {
+        store_limit_exempt_account(deps.storage, &account)?;
+    } else {
+        remove_limit_exempt_account(deps.storage, &account);
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_limit_exempt"),
+        attr("account", account.to_string()),
+        attr("exempt", exempt.to_string()),
+    ]))
+}
+
+// gov, the fund and this contract are always exempt
+fn is_limit_exempt(storage: &dyn Storage, env: &Env, account: &Addr) -> StdResult<bool> {
+    let seilor_config = read_seilor_config(storage)?;
+    Ok(*account == seilor_config.gov
+        || *account == seilor_config.fund
+        || *account == env.contract.address
+        || is_limit_exempt_account(storage, account))
+}
+
+/// Enforces the launch limits on a transfer of `amount` from `owner` to `recipient`.
+/// Runs after cw20-base moved the balance, so the recipient's new balance is checked.
+/// Amount and cooldown are skipped if either side is exempt, the wallet limit if the recipient is.
+pub fn assert_launch_limits(
+    storage: &mut dyn Storage,
+    env: &Env,
+    owner: &Addr,
+    recipient: &Addr,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    assert_batch_launch_limits(storage, env, owner, &[(recipient.clone(), amount)])
+}
+
+/// Enforces the launch limits on every leg of a batch from `owner`, the cooldown counts the
+/// batch as a single transfer.
+pub fn assert_batch_launch_limits(
+    storage: &mut dyn Storage,
+    env: &Env,
+    owner: &Addr,
+    transfers: &[(Addr, Uint128)],
+) -> Result<(), ContractError> {
+    let launch_limits = read_launch_limits(storage)?;
+    if launch_limits.lifted {
+        return Ok(());
+    }
+
+    let owner_exempt = is_limit_exempt(storage, env, owner)?;
+    let mut cooldown_applies = false;
+    for (recipient, amount) in transfers {
+        let recipient_exempt = is_limit_exempt(storage, env, recipient)?;
+        if !recipient_exempt && !owner_exempt {
+            if let Some(max_transfer_amount) = launch_limits.max_transfer_amount {
+                if *amount > max_transfer_amount {
+                    return Err(ContractError::MaxTransferExceeded(max_transfer_amount));
+                }
+            }
+            cooldown_applies = true;
+        }
+
+        if !recipient_exempt {
+            if let Some(max_wallet_balance) = launch_limits.max_wallet_balance {
+                let balance = BALANCES.may_load(storage, recipient)?.unwrap_or_default();
+                if balance > max_wallet_balance {
+                    return Err(ContractError::MaxWalletExceeded(recipient.to_string()));
+                }
+            }
+        }
+    }
+
+    if cooldown_applies && launch_limits.transfer_cooldown_seconds > 0 {
+        let now = env.block.time.seconds();
+        if let Some(last) = read_last_transfer_time(storage, owner)? {
+            let until = last + launch_limits.transfer_cooldown_seconds;
+            if now < until {
+                return Err(ContractError::TransferCooldown(until));
+            }
+        }
+        store_last_transfer_time(storage, owner, now)?;
+    }
+    Ok(())
+}
+
+pub fn transfer_locked(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    amount: Uint128,
+    release_at: u64,
+    cancelable: bool,
+) -> Result<Response, ContractError> {
+    assert_not_frozen(deps.storage, &info.sender)?;
+    let recipient = deps.api.addr_validate(&recipient)?;
+    if release_at <= env.block.time.seconds() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Invalid release_at",
+        )));
+    }
+
+    // the tax is charged as if the tokens went straight to the recipient
+    let tax = collect_transfer_tax(deps.storage, &info.sender, &recipient, amount)?;
+    let escrow = Escrow {
+        sender: info.sender.clone(),
+        recipient: recipient.clone(),
+        amount: tax.net_amount,
+        release_at,
+        cancelable,
     };
+    let id = next_escrow_id(deps.storage)?;
+    store_escrow(deps.storage, id, &escrow)?;
 
-    let mut cw20_res = execute_mint(
+    let mut users = vec![info.sender.clone(), env.contract.address.clone()];
+    users.extend(tax.receiver.clone());
+    execute_transfer(
         deps.branch(),
-        env,
-        sub_info,
-        user.clone().to_string(),
-        amount.clone(),
+        env.clone(),
+        info.clone(),
+        env.contract.address.to_string(),
+        tax.net_amount,
     )?;
-    // if cw20_res.is_err() {
-    //     return Err(ContractError::Std(StdError::generic_err(
-    //         cw20_res.err().unwrap().to_string(),
-    //     )));
-    // }
+    // held to the launch limits as a transfer to the recipient, the payout only checks its wallet
+    assert_launch_limits(deps.storage, &env, &info.sender, &recipient, amount)?;
 
-    // let mut res = cw20_res.unwrap();
+    let res = Response::new()
+        .add_attributes(vec![
+            attr("action", "transfer_locked"),
+            attr("id", id.to_string()),
+            attr("sender", info.sender.to_string()),
+            attr("recipient", recipient.to_string()),
+            attr("amount", tax.net_amount.to_string()),
+            attr("release_at", release_at.to_string()),
+        ])
+        .add_attributes(transfer_tax_attrs(&tax));
+    after_balance_change(deps, env, res, users)
+}
 
-    if let Some(contract) = contract {
-        if let Some(msg) = msg {
-            cw20_res = cw20_res.add_message(
-                Cw20MintReceiveMsg {
-                    sender: msg_sender.into(),
-                    amount,
-                    msg,
-                }
-                .into_cosmos_msg(contract)?,
-            );
+pub fn claim_locked(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let escrow =
+        read_escrow(deps.storage, id)?.ok_or_else(|| StdError::generic_err("Escrow not found"))?;
+    if info.sender != escrow.recipient {
+        return Err(ContractError::Unauthorized {});
+    }
+    if env.block.time.seconds() < escrow.release_at {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Escrow not released",
+        )));
+    }
+
+    let recipient = escrow.recipient.clone();
+    pay_out_escrow(deps, env, id, escrow, recipient, "claim_locked")
+}
+
+pub fn cancel_locked(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let escrow =
+        read_escrow(deps.storage, id)?.ok_or_else(|| StdError::generic_err("Escrow not found"))?;
+    if info.sender != escrow.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if !escrow.cancelable {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Escrow not cancelable",
+        )));
+    }
+    if env.block.time.seconds() >= escrow.release_at {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Escrow already released",
+        )));
+    }
+
+    let sender = escrow.sender.clone();
+    pay_out_escrow(deps, env, id, escrow, sender, "cancel_locked")
+}
+
+// removes the escrow and transfers the escrowed tokens from this contract to `to`
+fn pay_out_escrow(
+    mut deps: DepsMut,
+    env: Env,
+    id: u64,
+    escrow: Escrow,
+    to: Addr,
+    action: &str,
+) -> Result<Response, ContractError> {
+    remove_escrow(deps.storage, id, &escrow);
+    transfer_from_contract(deps.branch(), &env, &to, escrow.amount)?;
+
+    let res = Response::new().add_attributes(vec![
+        attr("action", action),
+        attr("id", id.to_string()),
+        attr("to", to.to_string()),
+        attr("amount", escrow.amount.to_string()),
+    ]);
+    let users = vec![env.contract.address.clone(), to];
+    after_balance_change(deps, env, res, users)
+}
+
+pub fn increase_periodic_allowance(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    spender: String,
+    amount_per_period: Uint128,
+    period_seconds: u64,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let spender = deps.api.addr_validate(&spender)?;
+    if spender == info.sender {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Cannot set allowance to own account",
+        )));
+    }
+    if period_seconds == 0 {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Invalid period_seconds",
+        )));
+    }
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Invalid expiration value",
+        )));
+    }
+
+    // a spender holds either a cw20 allowance or a periodic one, never both
+    if ALLOWANCES
+        .may_load(deps.storage, (&info.sender, &spender))?
+        .is_some_and(|allowance| !allowance.allowance.is_zero())
+    {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Spender already has a cw20 allowance",
+        )));
+    }
+
+    // the current period and what was spent in it carry over, unless the period length changes
+    let now = env.block.time.seconds();
+    let periodic_allowance = match read_periodic_allowance(deps.storage, &info.sender, &spender)? {
+        Some(periodic_allowance) if periodic_allowance.period_seconds == period_seconds => {
+            let periodic_allowance = refill_periodic_allowance(periodic_allowance, now);
+            PeriodicAllowance {
+                amount_per_period: periodic_allowance
+                    .amount_per_period
+                    .checked_add(amount_per_period)?,
+                period_seconds,
+                expires,
+                ..periodic_allowance
+            }
         }
+        Some(periodic_allowance) => PeriodicAllowance {
+            amount_per_period: periodic_allowance
+                .amount_per_period
+                .checked_add(amount_per_period)?,
+            period_seconds,
+            period_start: now,
+            spent: Uint128::zero(),
+            expires,
+        },
+        None => PeriodicAllowance {
+            amount_per_period,
+            period_seconds,
+            period_start: now,
+            spent: Uint128::zero(),
+            expires,
+        },
+    };
+    store_periodic_allowance(deps.storage, &info.sender, &spender, &periodic_allowance)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "increase_periodic_allowance"),
+        attr("owner", info.sender.to_string()),
+        attr("spender", spender.to_string()),
+        attr(
+            "amount_per_period",
+            periodic_allowance.amount_per_period.to_string(),
+        ),
+        attr("period_seconds", period_seconds.to_string()),
+    ]))
+}
+
+pub fn decrease_periodic_allowance(
+    deps: DepsMut,
+    info: MessageInfo,
+    spender: String,
+    amount_per_period: Uint128,
+) -> Result<Response, ContractError> {
+    let spender = deps.api.addr_validate(&spender)?;
+    let mut periodic_allowance = read_periodic_allowance(deps.storage, &info.sender, &spender)?
+        .ok_or_else(|| StdError::generic_err("Periodic allowance not found"))?;
+
+    // removed once nothing is left per period
+    periodic_allowance.amount_per_period = periodic_allowance
+        .amount_per_period
+        .saturating_sub(amount_per_period);
+    if periodic_allowance.amount_per_period.is_zero() {
+        remove_periodic_allowance(deps.storage, &info.sender, &spender);
+    } else {
+        store_periodic_allowance(deps.storage, &info.sender, &spender, &periodic_allowance)?;
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "decrease_periodic_allowance"),
+        attr("owner", info.sender.to_string()),
+        attr("spender", spender.to_string()),
+        attr(
+            "amount_per_period",
+            periodic_allowance.amount_per_period.to_string(),
+        ),
+    ]))
+}
+
+/// Rejects a cw20 allowance for a spender that has a periodic allowance, expired or not.
+pub fn assert_no_periodic_allowance(
+    storage: &dyn Storage,
+    owner: &Addr,
+    spender: &Addr,
+) -> Result<(), ContractError> {
+    if read_periodic_allowance(storage, owner, spender)?.is_some() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Spender already has a periodic allowance",
+        )));
+    }
+    Ok(())
+}
+
+/// Starts a new period with nothing spent if the current one is over.
+pub fn refill_periodic_allowance(
+    mut periodic_allowance: PeriodicAllowance,
+    now: u64,
+) -> PeriodicAllowance {
+    let elapsed_periods =
+        (now - periodic_allowance.period_start) / periodic_allowance.period_seconds;
+    if elapsed_periods > 0 {
+        periodic_allowance.period_start += elapsed_periods * periodic_allowance.period_seconds;
+        periodic_allowance.spent = Uint128::zero();
     }
+    periodic_allowance
+}
 
-    Ok(cw20_res)
+/// The cw20 allowance of an (owner, spender) pair, parked while a periodic allowance is spent.
+pub struct ParkedAllowance {
+    owner: Addr,
+    spender: Addr,
+    allowance: Option<AllowanceResponse>,
+}
+
+/// Spends `amount` of the spender's periodic allowance, if it has an unexpired one. The cw20
+/// allowance is then set to `amount` for the cw20 `*_from` handler to deduct, and must be put back
+/// with `restore_allowance` afterwards. Without a periodic allowance the cw20 allowance is used.
+pub fn spend_periodic_allowance(
+    storage: &mut dyn Storage,
+    env: &Env,
+    owner: &Addr,
+    spender: &Addr,
+    amount: Uint128,
+) -> Result<Option<ParkedAllowance>, ContractError> {
+    let periodic_allowance = match read_periodic_allowance(storage, owner, spender)? {
+        Some(periodic_allowance) if !periodic_allowance.expires.is_expired(&env.block) => {
+            periodic_allowance
+        }
+        _ => return Ok(None),
+    };
+
+    let mut periodic_allowance =
+        refill_periodic_allowance(periodic_allowance, env.block.time.seconds());
+    periodic_allowance.spent = periodic_allowance.spent.checked_add(amount)?;
+    if periodic_allowance.spent > periodic_allowance.amount_per_period {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Periodic allowance exceeded",
+        )));
+    }
+    store_periodic_allowance(storage, owner, spender, &periodic_allowance)?;
+
+    let parked = ParkedAllowance {
+        owner: owner.clone(),
+        spender: spender.clone(),
+        allowance: ALLOWANCES.may_load(storage, (owner, spender))?,
+    };
+    let allowance = AllowanceResponse {
+        allowance: amount,
+        expires: Expiration::Never {},
+    };
+    ALLOWANCES.save(storage, (owner, spender), &allowance)?;
+    ALLOWANCES_SPENDER.save(storage, (spender, owner), &allowance)?;
+    Ok(Some(parked))
+}
+
+pub fn restore_allowance(storage: &mut dyn Storage, parked: ParkedAllowance) -> StdResult<()> {
+    let owner_key = (&parked.owner, &parked.spender);
+    let spender_key = (&parked.spender, &parked.owner);
+    match parked.allowance {
+        Some(allowance) => {
+            ALLOWANCES.save(storage, owner_key, &allowance)?;
+            ALLOWANCES_SPENDER.save(storage, spender_key, &allowance)
+        }
+        None => {
+            ALLOWANCES.remove(storage, owner_key);
+            ALLOWANCES_SPENDER.remove(storage, spender_key);
+            Ok(())
+        }
+    }
+}
+
+pub fn create_stream(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    amount: Uint128,
+    start: u64,
+    end: u64,
+) -> Result<Response, ContractError> {
+    assert_not_frozen(deps.storage, &info.sender)?;
+    let recipient = deps.api.addr_validate(&recipient)?;
+    if start >= end || end <= env.block.time.seconds() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Invalid stream period",
+        )));
+    }
+
+    // the tax is charged as if the tokens went straight to the recipient
+    let tax = collect_transfer_tax(deps.storage, &info.sender, &recipient, amount)?;
+    let stream = Stream {
+        sender: info.sender.clone(),
+        recipient: recipient.clone(),
+        amount: tax.net_amount,
+        withdrawn: Uint128::zero(),
+        start,
+        end,
+    };
+    let id = next_stream_id(deps.storage)?;
+    store_stream(deps.storage, id, &stream)?;
+
+    let mut users = vec![info.sender.clone(), env.contract.address.clone()];
+    users.extend(tax.receiver.clone());
+    execute_transfer(
+        deps.branch(),
+        env.clone(),
+        info.clone(),
+        env.contract.address.to_string(),
+        tax.net_amount,
+    )?;
+    // held to the launch limits as a transfer to the recipient, the payout only checks its wallet
+    assert_launch_limits(deps.storage, &env, &info.sender, &recipient, amount)?;
+
+    let res = Response::new()
+        .add_attributes(vec![
+            attr("action", "create_stream"),
+            attr("id", id.to_string()),
+            attr("sender", info.sender.to_string()),
+            attr("recipient", recipient.to_string()),
+            attr("amount", tax.net_amount.to_string()),
+            attr("start", start.to_string()),
+            attr("end", end.to_string()),
+        ])
+        .add_attributes(transfer_tax_attrs(&tax));
+    after_balance_change(deps, env, res, users)
+}
+
+pub fn withdraw_stream(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let mut stream =
+        read_stream(deps.storage, id)?.ok_or_else(|| StdError::generic_err("Stream not found"))?;
+    if info.sender != stream.recipient {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let amount = stream_withdrawable(&stream, env.block.time.seconds());
+    if amount.is_zero() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Nothing to withdraw",
+        )));
+    }
+    stream.withdrawn += amount;
+    if stream.withdrawn == stream.amount {
+        remove_stream(deps.storage, id, &stream);
+    } else {
+        store_stream(deps.storage, id, &stream)?;
+    }
+    transfer_from_contract(deps.branch(), &env, &stream.recipient, amount)?;
+
+    let res = Response::new().add_attributes(vec![
+        attr("action", "withdraw_stream"),
+        attr("id", id.to_string()),
+        attr("recipient", stream.recipient.to_string()),
+        attr("amount", amount.to_string()),
+    ]);
+    let users = vec![env.contract.address.clone(), stream.recipient];
+    after_balance_change(deps, env, res, users)
+}
+
+pub fn cancel_stream(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let stream =
+        read_stream(deps.storage, id)?.ok_or_else(|| StdError::generic_err("Stream not found"))?;
+    if info.sender != stream.sender && info.sender != stream.recipient {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // the recipient keeps what has accrued, the sender gets the rest back
+    let recipient_amount = stream_withdrawable(&stream, env.block.time.seconds());
+    let sender_amount = stream.amount - stream.withdrawn - recipient_amount;
+    remove_stream(deps.storage, id, &stream);
+    if !recipient_amount.is_zero() {
+        transfer_from_contract(deps.branch(), &env, &stream.recipient, recipient_amount)?;
+    }
+    if !sender_amount.is_zero() {
+        transfer_from_contract(deps.branch(), &env, &stream.sender, sender_amount)?;
+    }
+
+    let res = Response::new().add_attributes(vec![
+        attr("action", "cancel_stream"),
+        attr("id", id.to_string()),
+        attr("recipient_amount", recipient_amount.to_string()),
+        attr("sender_amount", sender_amount.to_string()),
+    ]);
+    let users = vec![
+        env.contract.address.clone(),
+        stream.recipient,
+        stream.sender,
+    ];
+    after_balance_change(deps, env, res, users)
+}
+
+/// Amount streamed to the recipient by `now`, linear between start and end.
+pub fn stream_accrued(stream: &Stream, now: u64) -> Uint128 {
+    if now <= stream.start {
+        Uint128::zero()
+    } else if now >= stream.end {
+        stream.amount
+    } else {
+        stream
+            .amount
+            .multiply_ratio(now - stream.start, stream.end - stream.start)
+    }
+}
+
+pub fn stream_withdrawable(stream: &Stream, now: u64) -> Uint128 {
+    stream_accrued(stream, now) - stream.withdrawn
+}
+
+// moves tokens held by this contract for escrows and streams, the caller runs `after_balance_change`.
+// The contract is limit exempt, so of the launch limits only the recipient's wallet limit applies.
+fn transfer_from_contract(
+    mut deps: DepsMut,
+    env: &Env,
+    to: &Addr,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let contract_info = MessageInfo {
+        sender: env.contract.address.clone(),
+        funds: vec![],
+    };
+    execute_transfer(
+        deps.branch(),
+        env.clone(),
+        contract_info,
+        to.to_string(),
+        amount,
+    )?;
+    assert_launch_limits(deps.storage, env, &env.contract.address, to, amount)
+}
+
+pub fn recover_tokens(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: RecoverAsset,
+    recipient: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let seilor_config = read_seilor_config(deps.storage)?;
+    if info.sender != seilor_config.gov {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let (asset_attr, msg) = match asset {
+        RecoverAsset::Native { denom } => {
+            if read_wrap_denom(deps.storage)?.as_ref() == Some(&denom) {
+                return Err(ContractError::Std(StdError::generic_err(
+                    "Cannot recover SEILOR",
+                )));
+            }
+            (
+                denom.clone(),
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: recipient.to_string(),
+                    amount: coins(amount.u128(), denom),
+                }),
+            )
+        }
+        RecoverAsset::Cw20 { contract_addr } => {
+            let contract_addr = deps.api.addr_validate(&contract_addr)?;
+            if contract_addr == env.contract.address {
+                return Err(ContractError::Std(StdError::generic_err(
+                    "Cannot recover SEILOR",
+                )));
+            }
+            (
+                contract_addr.to_string(),
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: contract_addr.to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: recipient.to_string(),
+                        amount,
+                    })?,
+                    funds: vec![],
+                }),
+            )
+        }
+    };
+
+    Ok(Response::new().add_message(msg).add_attributes(vec![
+        attr("action", "recover_tokens"),
+        attr("asset", asset_attr),
+        attr("recipient", recipient.to_string()),
+        attr("amount", amount.to_string()),
+    ]))
+}
+
+pub fn create_wrap_denom<C: TokenFactory>(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    subdenom: String,
+) -> Result<Response<C>, ContractError> {
+    let seilor_config = read_seilor_config(deps.storage)?;
+    if info.sender != seilor_config.gov {
+        return Err(ContractError::Unauthorized {});
+    }
+    if read_wrap_denom(deps.storage)?.is_some() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Wrap denom already created",
+        )));
+    }
+
+    let denom = token_factory_denom(env.contract.address.as_str(), &subdenom);
+    store_wrap_denom(deps.storage, &denom)?;
+
+    Ok(Response::new()
+        .add_message(C::create_denom(&env.contract.address, &subdenom))
+        .add_attributes(vec![
+            attr("action", "create_wrap_denom"),
+            attr("denom", denom),
+        ]))
+}
+
+fn read_wrap_denom_or_err(storage: &dyn Storage) -> Result<String, ContractError> {
+    read_wrap_denom(storage)?
+        .ok_or_else(|| ContractError::Std(StdError::generic_err("Wrap denom not created")))
+}
+
+// locks cw20 SEILOR in the contract and mints the same amount of the wrap denom to the sender
+pub fn wrap<C: TokenFactory>(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response<C>, ContractError> {
+    assert_not_frozen(deps.storage, &info.sender)?;
+    let denom = read_wrap_denom_or_err(deps.storage)?;
+
+    // not a payment, so no transfer tax
+    execute_transfer(
+        deps.branch(),
+        env.clone(),
+        info.clone(),
+        env.contract.address.to_string(),
+        amount,
+    )?;
+    let wrapped_supply = read_wrapped_supply(deps.storage)?.checked_add(amount)?;
+    store_wrapped_supply(deps.storage, &wrapped_supply)?;
+
+    let users = vec![info.sender.clone(), env.contract.address.clone()];
+    let res = Response::new().add_attributes(vec![
+        attr("action", "wrap"),
+        attr("sender", info.sender.to_string()),
+        attr("amount", amount.to_string()),
+        attr("denom", denom.clone()),
+    ]);
+    let contract_addr = env.contract.address.clone();
+    let res = after_balance_change(deps, env, res, users)?;
+
+    Ok(into_custom_response(res)?
+        .add_message(C::mint_tokens(
+            &contract_addr,
+            &coin(amount.u128(), denom.clone()),
+        ))
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: coins(amount.u128(), denom),
+        }))
+}
+
+// burns the wrap denom sent along and releases the same amount of cw20 SEILOR to the sender,
+// less the transfer tax
+pub fn unwrap<C: TokenFactory>(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response<C>, ContractError> {
+    assert_not_frozen(deps.storage, &info.sender)?;
+    let denom = read_wrap_denom_or_err(deps.storage)?;
+    let amount = must_pay(&info, &denom).map_err(|err| StdError::generic_err(err.to_string()))?;
+
+    let wrapped_supply = read_wrapped_supply(deps.storage)?.checked_sub(amount)?;
+    store_wrapped_supply(deps.storage, &wrapped_supply)?;
+    // the denom changes hands untaxed and unlimited, so releasing it counts as a transfer to
+    // the sender: the tax comes out of the released amount and the sender's limits apply
+    assert_launch_limits(deps.storage, &env, &info.sender, &info.sender, amount)?;
+    let tax = collect_transfer_tax(deps.storage, &env.contract.address, &info.sender, amount)?;
+    transfer_from_contract(deps.branch(), &env, &info.sender, tax.net_amount)?;
+
+    let mut users = vec![env.contract.address.clone(), info.sender.clone()];
+    users.extend(tax.receiver.clone());
+    let res = Response::new()
+        .add_attributes(vec![
+            attr("action", "unwrap"),
+            attr("sender", info.sender.to_string()),
+            attr("amount", amount.to_string()),
+            attr("denom", denom.clone()),
+        ])
+        .add_attributes(transfer_tax_attrs(&tax));
+    let contract_addr = env.contract.address.clone();
+    let res = after_balance_change(deps, env, res, users)?;
+
+    Ok(into_custom_response(res)?
+        .add_message(C::burn_tokens(&contract_addr, &coin(amount.u128(), denom))))
+}
+
+pub fn set_supply_excluded(
+    deps: DepsMut,
+    info: MessageInfo,
+    account: String,
+    excluded: bool,
+) -> Result<Response, ContractError> {
+    let seilor_config = read_seilor_config(deps.storage)?;
+    if info.sender != seilor_config.gov {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let account = deps.api.addr_validate(&account)?;
+    if excluded {
+        let accounts = read_all_supply_excluded_accounts(deps.storage)?;
+        if !accounts.contains(&account) && accounts.len() >= MAX_SUPPLY_EXCLUDED_ACCOUNTS {
+            return Err(ContractError::Std(StdError::generic_err(
+                "Too many supply excluded accounts",
+            )));
+        }
+        store_supply_excluded_account(deps.storage, &account)?;
+    } else {
+        remove_supply_excluded_account(deps.storage, &account);
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_supply_excluded"),
+        attr("account", account.to_string()),
+        attr("excluded", excluded.to_string()),
+    ]))
+}
+
+/// Tax charged on a single transfer, see `compute_transfer_tax`.
+pub struct TransferTax {
+    pub tax_amount: Uint128,
+    pub burn_amount: Uint128,
+    // None when nothing goes to the receiver
+    pub receiver: Option<Addr>,
+    pub receiver_amount: Uint128,
+    pub net_amount: Uint128,
+}
+
+/// Returns the configured tax receiver, falling back to the fund. Empty if neither is set.
+pub fn transfer_tax_receiver(storage: &dyn Storage) -> StdResult<Addr> {
+    let tax_config = read_transfer_tax_config(storage)?;
+    if !is_empty_str(tax_config.receiver.as_str()) {
+        return Ok(tax_config.receiver);
+    }
+    Ok(read_seilor_config(storage)?.fund)
+}
+
+/// Computes the tax on moving `amount` from `owner` to `recipient`.
+/// Nothing is charged if either side is exempt, and the whole tax is burned when there is no receiver.
+pub fn compute_transfer_tax(
+    storage: &dyn Storage,
+    owner: &Addr,
+    recipient: &Addr,
+    amount: Uint128,
+) -> StdResult<TransferTax> {
+    let tax_config = read_transfer_tax_config(storage)?;
+    if tax_config.tax_bps == 0
+        || is_tax_exempt_account(storage, owner)
+        || is_tax_exempt_account(storage, recipient)
+    {
+        return Ok(TransferTax {
+            tax_amount: Uint128::zero(),
+            burn_amount: Uint128::zero(),
+            receiver: None,
+            receiver_amount: Uint128::zero(),
+            net_amount: amount,
+        });
+    }
+
+    let tax_amount = amount.multiply_ratio(tax_config.tax_bps, BPS_DENOMINATOR);
+    let receiver = transfer_tax_receiver(storage)?;
+    let burn_amount = if is_empty_str(receiver.as_str()) {
+        tax_amount
+    } else {
+        tax_amount.multiply_ratio(tax_config.burn_bps, BPS_DENOMINATOR)
+    };
+    let receiver_amount = tax_amount - burn_amount;
+
+    Ok(TransferTax {
+        tax_amount,
+        burn_amount,
+        receiver: if receiver_amount.is_zero() {
+            None
+        } else {
+            Some(receiver)
+        },
+        receiver_amount,
+        net_amount: amount - tax_amount,
+    })
+}
+
+/// Takes the transfer tax from `owner` up front, burning its burn share and crediting the
+/// receiver, so that the cw20 transfer itself only moves the net amount.
+pub fn collect_transfer_tax(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    recipient: &Addr,
+    amount: Uint128,
+) -> Result<TransferTax, ContractError> {
+    let tax = compute_transfer_tax(storage, owner, recipient, amount)?;
+    if tax.tax_amount.is_zero() {
+        return Ok(tax);
+    }
+
+    BALANCES.update(storage, owner, |balance| -> StdResult<_> {
+        Ok(balance.unwrap_or_default().checked_sub(tax.tax_amount)?)
+    })?;
+    if !tax.burn_amount.is_zero() {
+        TOKEN_INFO.update(storage, |mut token_info| -> StdResult<_> {
+            token_info.total_supply = token_info.total_supply.checked_sub(tax.burn_amount)?;
+            Ok(token_info)
+        })?;
+        add_burned(storage, owner, tax.burn_amount)?;
+    }
+    if let Some(receiver) = &tax.receiver {
+        BALANCES.update(storage, receiver, |balance| -> StdResult<_> {
+            Ok(balance
+                .unwrap_or_default()
+                .checked_add(tax.receiver_amount)?)
+        })?;
+    }
+
+    Ok(tax)
+}
+
+pub fn transfer_tax_attrs(tax: &TransferTax) -> Vec<Attribute> {
+    if tax.tax_amount.is_zero() {
+        return vec![];
+    }
+    let mut attrs = vec![
+        attr("tax_amount", tax.tax_amount.to_string()),
+        attr("tax_burn_amount", tax.burn_amount.to_string()),
+    ];
+    if let Some(receiver) = &tax.receiver {
+        attrs.push(attr("tax_receiver", receiver.to_string()));
+        attrs.push(attr("tax_receiver_amount", tax.receiver_amount.to_string()));
+    }
+    attrs
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn permit(
+    deps: DepsMut,
+    env: Env,
+    owner: String,
+    spender: String,
+    amount: Uint128,
+    expires: Option<Expiration>,
+    nonce: u64,
+    signature: Binary,
+    pubkey: Binary,
+) -> Result<Response, ContractError> {
+    let owner = deps.api.addr_validate(&owner)?;
+
+    let expected_nonce = read_permit_nonce(deps.storage, &owner)?;
+    if nonce != expected_nonce {
+        return Err(ContractError::InvalidPermitNonce(expected_nonce));
+    }
+
+    // the pubkey must belong to the owner
+    let prefix = owner.as_str().split('1').next().unwrap_or_default();
+    if pubkey_to_address(&pubkey, prefix).ok().as_deref() != Some(owner.as_str()) {
+        return Err(ContractError::InvalidPermitSignature {});
+    }
+
+    let permit_msg = PermitMsg {
+        chain_id: env.block.chain_id.clone(),
+        contract: env.contract.address.to_string(),
+        owner: owner.to_string(),
+        spender: spender.clone(),
+        amount,
+        expires,
+        nonce,
+    };
+    let hash = Sha256::digest(to_vec(&permit_msg)?);
+    // malformed signatures fail verification as well
+    let verified = deps
+        .api
+        .secp256k1_verify(&hash, &signature, &pubkey)
+        .unwrap_or(false);
+    if !verified {
+        return Err(ContractError::InvalidPermitSignature {});
+    }
+    let spender_addr = deps.api.addr_validate(&spender)?;
+    assert_no_periodic_allowance(deps.storage, &owner, &spender_addr)?;
+    store_permit_nonce(deps.storage, &owner, nonce + 1)?;
+
+    let owner_info = MessageInfo {
+        sender: owner,
+        funds: vec![],
+    };
+    let res = execute_increase_allowance(deps, env, owner_info, spender, amount, expires)?;
+    Ok(res.add_attributes(vec![
+        attr("permit", "true"),
+        attr("nonce", nonce.to_string()),
+    ]))
+}
+
+pub fn mint(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    user: Addr,
+    amount: Uint128,
+    contract: Option<String>,
+    msg: Option<Binary>,
+) -> Result<Response, ContractError> {
+    // the hook is only sent along with a msg
+    let hook = match (contract, msg) {
+        (Some(contract), Some(msg)) => Some((deps.api.addr_validate(&contract)?, msg)),
+        _ => None,
+    };
+    if !within_mint_approval_threshold(deps.storage, &env, &info.sender, amount)? {
+        return propose_mint(deps, env, info.sender, user, amount, hook);
+    }
+    mint_with_hook(deps, env, info.sender, user, amount, hook)
+}
+
+fn mint_with_hook(
+    mut deps: DepsMut,
+    env: Env,
+    msg_sender: Addr,
+    user: Addr,
+    amount: Uint128,
+    hook: Option<(Addr, Binary)>,
+) -> Result<Response, ContractError> {
+    let hook_contract = hook.as_ref().map(|(contract, _)| contract.clone());
+    let (mut cw20_res, mint_id) = mint_to(
+        deps.branch(),
+        env.clone(),
+        &msg_sender,
+        &user,
+        amount,
+        hook_contract.clone(),
+    )?;
+    // if cw20_res.is_err() {
+    //     return Err(ContractError::Std(StdError::generic_err(
+    //         cw20_res.err().unwrap().to_string(),
+    //     )));
+    // }
+
+    // let mut res = cw20_res.unwrap();
+
+    if let Some((contract, msg)) = hook {
+        // a failing hook doesn't revert the mint, the reply carries the mint id to `mint_hook_failed`
+        let hook_msg = Cw20MintReceiveMsg {
+            sender: msg_sender.into(),
+            amount,
+            msg,
+        }
+        .into_cosmos_msg(contract)?;
+        cw20_res = cw20_res.add_submessage(SubMsg::reply_on_error(hook_msg, mint_id));
+    }
+
+    // the refresh goes out after the hook, so it sees the balance once an escrow has settled
+    after_balance_change(deps.branch(), env, cw20_res, vec![user])
+}
+
+// counts the mint toward the minter's current approval window if that stays within the
+// threshold, so splitting a mint doesn't get around it
+fn within_mint_approval_threshold(
+    storage: &mut dyn Storage,
+    env: &Env,
+    minter: &Addr,
+    amount: Uint128,
+) -> StdResult<bool> {
+    let mint_approval_config = read_mint_approval_config(storage)?;
+    let threshold = match mint_approval_config.threshold {
+        Some(threshold) => threshold,
+        None => return Ok(true),
+    };
+
+    let now = env.block.time.seconds();
+    let mut mint_window = read_mint_window(storage, minter)?;
+    if now >= mint_window.start + mint_approval_config.window_seconds {
+        mint_window = MintWindow {
+            start: now,
+            minted: Uint128::zero(),
+        };
+    }
+    mint_window.minted = mint_window.minted.checked_add(amount)?;
+    if mint_window.minted > threshold {
+        return Ok(false);
+    }
+    store_mint_window(storage, minter, &mint_window)?;
+    Ok(true)
+}
+
+// parks a mint above the approval threshold until gov approves it, the amount is reserved
+// from the quota now and checked again when it is executed
+fn propose_mint(
+    deps: DepsMut,
+    env: Env,
+    minter: Addr,
+    user: Addr,
+    amount: Uint128,
+    hook: Option<(Addr, Binary)>,
+) -> Result<Response, ContractError> {
+    let mut minter_info = match read_minter_info(deps.storage, &minter)? {
+        Some(minter_info) => minter_info,
+        None => return Err(ContractError::Unauthorized {}),
+    };
+    minter_info.minted = minter_info.minted.checked_add(amount)?;
+    if minter_info.minted > minter_info.quota {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Minter quota exceeded",
+        )));
+    }
+    store_minter_info(deps.storage, &minter, &minter_info)?;
+
+    let mint_approval_config = read_mint_approval_config(deps.storage)?;
+    let now = env.block.time.seconds();
+    let (contract, msg) = match hook {
+        Some((contract, msg)) => (Some(contract), Some(msg)),
+        None => (None, None),
+    };
+    let mint_proposal = MintProposal {
+        minter: minter.clone(),
+        recipient: user.clone(),
+        amount,
+        contract,
+        msg,
+        created_at: now,
+        expires_at: now + mint_approval_config.window_seconds,
+    };
+    let id = append_mint_proposal(deps.storage, &mint_proposal)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "propose_mint"),
+        attr("proposal_id", id.to_string()),
+        attr("minter", minter.to_string()),
+        attr("recipient", user.to_string()),
+        attr("amount", amount.to_string()),
+    ]))
+}
+
+pub fn set_mint_approval(
+    deps: DepsMut,
+    info: MessageInfo,
+    threshold: Option<Uint128>,
+    window_seconds: u64,
+) -> Result<Response, ContractError> {
+    let seilor_config = read_seilor_config(deps.storage)?;
+    if info.sender != seilor_config.gov {
+        return Err(ContractError::Unauthorized {});
+    }
+    if threshold.is_some() && window_seconds == 0 {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Invalid window_seconds",
+        )));
+    }
+
+    let mint_approval_config = MintApprovalConfig {
+        threshold,
+        window_seconds,
+    };
+    store_mint_approval_config(deps.storage, &mint_approval_config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_mint_approval"),
+        attr("window_seconds", window_seconds.to_string()),
+    ]))
+}
+
+// gives the amount reserved by a proposal back to its minter's quota
+fn release_mint_reservation(
+    storage: &mut dyn Storage,
+    mint_proposal: &MintProposal,
+) -> StdResult<()> {
+    if let Some(mut minter_info) = read_minter_info(storage, &mint_proposal.minter)? {
+        minter_info.minted = minter_info.minted.saturating_sub(mint_proposal.amount);
+        store_minter_info(storage, &mint_proposal.minter, &minter_info)?;
+    }
+    Ok(())
+}
+
+fn read_mint_proposal_or_err(
+    storage: &dyn Storage,
+    id: u64,
+) -> Result<MintProposal, ContractError> {
+    match read_mint_proposal(storage, id)? {
+        Some(mint_proposal) => Ok(mint_proposal),
+        None => Err(ContractError::Std(StdError::generic_err(
+            "Mint proposal not found",
+        ))),
+    }
+}
+
+pub fn approve_mint(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let seilor_config = read_seilor_config(deps.storage)?;
+    if info.sender != seilor_config.gov {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mint_proposal = read_mint_proposal_or_err(deps.storage, id)?;
+    if env.block.time.seconds() >= mint_proposal.expires_at {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Mint proposal expired",
+        )));
+    }
+    remove_mint_proposal(deps.storage, id);
+    // the mint takes the quota again
+    release_mint_reservation(deps.storage, &mint_proposal)?;
+
+    let hook = match (mint_proposal.contract, mint_proposal.msg) {
+        (Some(contract), Some(msg)) => Some((contract, msg)),
+        _ => None,
+    };
+    let res = mint_with_hook(
+        deps,
+        env,
+        mint_proposal.minter,
+        mint_proposal.recipient,
+        mint_proposal.amount,
+        hook,
+    )?;
+    Ok(res.add_attributes(vec![
+        attr("action", "approve_mint"),
+        attr("proposal_id", id.to_string()),
+    ]))
+}
+
+pub fn reject_mint(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let mint_proposal = read_mint_proposal_or_err(deps.storage, id)?;
+    // anyone can clear an expired proposal
+    let seilor_config = read_seilor_config(deps.storage)?;
+    if info.sender != seilor_config.gov && env.block.time.seconds() < mint_proposal.expires_at {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    remove_mint_proposal(deps.storage, id);
+    release_mint_reservation(deps.storage, &mint_proposal)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "reject_mint"),
+        attr("proposal_id", id.to_string()),
+    ]))
+}
+
+// moves the tokens of a mint whose hook failed from the recipient into its mint escrow
+pub fn mint_hook_failed(
+    deps: DepsMut,
+    env: Env,
+    mint_id: u64,
+    error: String,
+) -> Result<Response, ContractError> {
+    let mint_record = read_mint_record(deps.storage, mint_id)?;
+    let contract = mint_record
+        .contract
+        .clone()
+        .ok_or_else(|| StdError::generic_err("Mint hook not found"))?;
+    let recipient = mint_record.recipient.clone();
+
+    // the hook's writes are reverted, so the recipient still holds the minted tokens. They are
+    // moved directly, a vesting or frozen recipient must not make the reply revert the mint
+    BALANCES.update(deps.storage, &recipient, |balance| -> StdResult<_> {
+        Ok(balance
+            .unwrap_or_default()
+            .checked_sub(mint_record.amount)?)
+    })?;
+    BALANCES.update(
+        deps.storage,
+        &env.contract.address,
+        |balance| -> StdResult<_> {
+            Ok(balance
+                .unwrap_or_default()
+                .checked_add(mint_record.amount)?)
+        },
+    )?;
+    let escrowed = read_mint_escrow(deps.storage, &recipient)?.checked_add(mint_record.amount)?;
+    store_mint_escrow(deps.storage, &recipient, &escrowed)?;
+
+    let failure = MintHookFailure {
+        minter: mint_record.minter,
+        recipient: recipient.clone(),
+        contract,
+        amount: mint_record.amount,
+        error,
+        height: env.block.height,
+        timestamp: env.block.time.seconds(),
+    };
+    store_mint_hook_failure(deps.storage, mint_id, &failure)?;
+
+    // the recipient's refresh is already queued after the hook by the mint
+    let users = vec![recipient.clone(), env.contract.address.clone()];
+    checkpoint_balances(deps.storage, env.block.height, &users)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "mint_hook_failed"),
+        attr("mint_id", mint_id.to_string()),
+        attr("recipient", recipient.to_string()),
+        attr("amount", mint_record.amount.to_string()),
+    ]))
+}
+
+pub fn set_emission_schedule(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    curve: EmissionCurve,
+    receivers: Vec<EmissionReceiver>,
+) -> Result<Response, ContractError> {
+    let seilor_config = read_seilor_config(deps.storage)?;
+    if info.sender != seilor_config.gov {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let valid_curve = match &curve {
+        EmissionCurve::Piecewise { segments } => {
+            !segments.is_empty() && segments.windows(2).all(|w| w[0].start < w[1].start)
+        }
+        EmissionCurve::Halving { epoch_seconds, .. } => *epoch_seconds > 0,
+    };
+    if !valid_curve {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Invalid emission curve",
+        )));
+    }
+    if receivers.is_empty() || receivers.iter().any(|r| r.weight == 0) {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Invalid emission receivers",
+        )));
+    }
+    let receivers = receivers
+        .into_iter()
+        .map(|r| Ok((deps.api.addr_validate(&r.address)?, r.weight)))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // settle the previous schedule up to now
+    let (res, users) = match read_emission_config(deps.storage)? {
+        Some(emission_config) => emit_accrued(deps.branch(), &env, &emission_config)?,
+        None => (Response::new(), vec![]),
+    };
+
+    let emission_config = EmissionConfig {
+        curve,
+        receivers,
+        last_emitted: env.block.time.seconds(),
+    };
+    store_emission_config(deps.storage, &emission_config)?;
+
+    let res = res.add_attributes(vec![attr("action", "set_emission_schedule")]);
+    after_balance_change(deps, env, res, users)
+}
+
+pub fn emit(mut deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let mut emission_config = match read_emission_config(deps.storage)? {
+        Some(emission_config) => emission_config,
+        None => {
+            return Err(ContractError::Std(StdError::generic_err(
+                "Emission schedule not set",
+            )))
+        }
+    };
+
+    let (res, users) = emit_accrued(deps.branch(), &env, &emission_config)?;
+    emission_config.last_emitted = env.block.time.seconds();
+    store_emission_config(deps.storage, &emission_config)?;
+
+    after_balance_change(deps, env, res, users)
+}
+
+// mints what accrued since last_emitted to the receivers by weight, the last receiver
+// gets the rounding dust. Accrual above max_supply is dropped.
+fn emit_accrued(
+    mut deps: DepsMut,
+    env: &Env,
+    emission_config: &EmissionConfig,
+) -> Result<(Response, Vec<Addr>), ContractError> {
+    let accrued = emission_accrued(
+        &emission_config.curve,
+        emission_config.last_emitted,
+        env.block.time.seconds(),
+    )?;
+    let seilor_config = read_seilor_config(deps.storage)?;
+    let max_supply = Uint128::from(seilor_config.max_supply);
+    let total_supply = TOKEN_INFO.load(deps.storage)?.total_supply;
+    let amount = accrued.min(max_supply.saturating_sub(total_supply));
+
+    let mut res = Response::new().add_attributes(vec![
+        attr("action", "emit"),
+        attr("amount", amount.to_string()),
+    ]);
+    let mut users = vec![];
+    if amount.is_zero() {
+        return Ok((res, users));
+    }
+
+    let total_weight: u64 = emission_config.receivers.iter().map(|(_, w)| w).sum();
+    let mut left = amount;
+    for (i, (receiver, weight)) in emission_config.receivers.iter().enumerate() {
+        let share = if i + 1 == emission_config.receivers.len() {
+            left
+        } else {
+            amount.multiply_ratio(*weight, total_weight)
+        };
+        if share.is_zero() {
+            continue;
+        }
+        left -= share;
+
+        if !seilor_config.mint_to_frozen_enabled {
+            assert_not_frozen(deps.storage, receiver)?;
+        }
+        let mint_record = MintRecord {
+            minter: env.contract.address.clone(),
+            recipient: receiver.clone(),
+            amount: share,
+            height: env.block.height,
+            timestamp: env.block.time.seconds(),
+            contract: None,
+        };
+        let id = append_mint_record(deps.storage, &mint_record)?;
+
+        let sub_info = MessageInfo {
+            sender: env.contract.address.clone(),
+            funds: vec![],
+        };
+        execute_mint(
+            deps.branch(),
+            env.clone(),
+            sub_info,
+            receiver.to_string(),
+            share,
+        )?;
+        res = res.add_attributes(vec![
+            attr("receiver", receiver.to_string()),
+            attr("share", share.to_string()),
+            attr("mint_id", id.to_string()),
+        ]);
+        users.push(receiver.clone());
+    }
+
+    Ok((res, users))
+}
+
+/// Amount the emission curve accrues between block times `from` and `to`.
+pub fn emission_accrued(curve: &EmissionCurve, from: u64, to: u64) -> StdResult<Uint128> {
+    // rate * overlap of [start, end) with [from, to)
+    let accrue = |total: Uint128, rate: Uint128, start: u64, end: u64| -> StdResult<Uint128> {
+        let lo = start.max(from);
+        let hi = end.min(to);
+        if lo >= hi {
+            return Ok(total);
+        }
+        Ok(total.checked_add(rate.checked_mul(Uint128::from(hi - lo))?)?)
+    };
+
+    let mut total = Uint128::zero();
+    match curve {
+        EmissionCurve::Piecewise { segments } => {
+            for (i, segment) in segments.iter().enumerate() {
+                let end = segments.get(i + 1).map_or(u64::MAX, |next| next.start);
+                total = accrue(total, segment.rate_per_second, segment.start, end)?;
+            }
+        }
+        EmissionCurve::Halving {
+            start,
+            initial_rate,
+            epoch_seconds,
+        } => {
+            let mut rate = *initial_rate;
+            let mut epoch_start = *start;
+            while !rate.is_zero() && epoch_start < to {
+                let epoch_end = epoch_start.saturating_add(*epoch_seconds);
+                total = accrue(total, rate, epoch_start, epoch_end)?;
+                rate >>= 1;
+                epoch_start = epoch_end;
+            }
+        }
+    }
+    Ok(total)
+}
+
+pub fn create_vesting(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    amount: Uint128,
+    schedule: VestingSchedule,
+) -> Result<Response, ContractError> {
+    let seilor_config = read_seilor_config(deps.storage)?;
+    let is_minter = read_minter_info(deps.storage, &info.sender)?.is_some();
+    if !is_minter && info.sender != seilor_config.gov {
+        return Err(ContractError::Unauthorized {});
+    }
+    if amount.is_zero() {
+        return Err(ContractError::Std(StdError::generic_err("Invalid amount")));
+    }
+    if let VestingSchedule::Linear { start, end } = schedule {
+        if start >= end {
+            return Err(ContractError::Std(StdError::generic_err(
+                "Invalid vesting schedule",
+            )));
+        }
+    }
+
+    let recipient = deps.api.addr_validate(&recipient)?;
+    if let Some(vesting_account) = read_vesting_account(deps.storage, &recipient)? {
+        if !vesting_locked(&vesting_account, env.block.time.seconds()).is_zero() {
+            return Err(ContractError::Std(StdError::generic_err(
+                "Vesting account already exists",
+            )));
+        }
+    }
+
+    let mut users = vec![recipient.clone()];
+    let res = if is_minter {
+        if !within_mint_approval_threshold(deps.storage, &env, &info.sender, amount)? {
+            return Err(ContractError::Std(StdError::generic_err(
+                "Mint above approval threshold",
+            )));
+        }
+        let (res, _) = mint_to(
+            deps.branch(),
+            env.clone(),
+            &info.sender,
+            &recipient,
+            amount,
+            None,
+        )?;
+        res
+    } else {
+        users.push(info.sender.clone());
+        execute_transfer(
+            deps.branch(),
+            env.clone(),
+            info.clone(),
+            recipient.to_string(),
+            amount,
+        )?
+    };
+    let vesting_account = VestingAccount { amount, schedule };
+    store_vesting_account(deps.storage, &recipient, &vesting_account)?;
+
+    let res = res.add_attributes(vec![
+        attr("action", "create_vesting"),
+        attr("recipient", recipient.to_string()),
+        attr("vesting_amount", amount.to_string()),
+    ]);
+    after_balance_change(deps, env, res, users)
+}
+
+/// The part of a vesting account not unlocked at `now`.
+pub fn vesting_locked(vesting_account: &VestingAccount, now: u64) -> Uint128 {
+    let amount = vesting_account.amount;
+    match vesting_account.schedule {
+        VestingSchedule::Linear { start, end } => {
+            if now <= start {
+                amount
+            } else if now >= end {
+                Uint128::zero()
+            } else {
+                amount - amount.multiply_ratio(now - start, end - start)
+            }
+        }
+        VestingSchedule::Cliff { release_at } => {
+            if now < release_at {
+                amount
+            } else {
+                Uint128::zero()
+            }
+        }
+    }
+}
+
+// a vesting account can't go below its locked amount
+fn assert_vesting_unlocked(
+    storage: &dyn Storage,
+    env: &Env,
+    account: &Addr,
+) -> Result<(), ContractError> {
+    if let Some(vesting_account) = read_vesting_account(storage, account)? {
+        let locked = vesting_locked(&vesting_account, env.block.time.seconds());
+        let balance = BALANCES.may_load(storage, account)?.unwrap_or_default();
+        if balance < locked {
+            return Err(ContractError::VestingLocked(account.to_string()));
+        }
+    }
+    Ok(())
+}
+
+pub fn claim_mint_escrow(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let amount = read_mint_escrow(deps.storage, &info.sender)?;
+    if amount.is_zero() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Nothing to claim",
+        )));
+    }
+
+    remove_mint_escrow(deps.storage, &info.sender);
+    transfer_from_contract(deps.branch(), &env, &info.sender, amount)?;
+
+    let res = Response::new().add_attributes(vec![
+        attr("action", "claim_mint_escrow"),
+        attr("recipient", info.sender.to_string()),
+        attr("amount", amount.to_string()),
+    ]);
+    let users = vec![env.contract.address.clone(), info.sender];
+    after_balance_change(deps, env, res, users)
+}
+
+/// Adds `amount` to the total and per account burn counters.
+pub fn add_burned(storage: &mut dyn Storage, account: &Addr, amount: Uint128) -> StdResult<()> {
+    let total_burned = read_total_burned(storage)?.checked_add(amount)?;
+    store_total_burned(storage, &total_burned)?;
+    let burned = read_burned(storage, account)?.checked_add(amount)?;
+    store_burned(storage, account, &burned)
+}
+
+/// Counts an explicit burn and appends it to the burn log. Transfer tax burns are only counted.
+pub fn record_burn(
+    storage: &mut dyn Storage,
+    env: &Env,
+    account: &Addr,
+    amount: Uint128,
+    reason: Option<String>,
+) -> Result<Vec<Attribute>, ContractError> {
+    if let Some(reason) = &reason {
+        if reason.len() > MAX_BURN_REASON_LEN {
+            return Err(ContractError::Std(StdError::generic_err(
+                "Burn reason too long",
+            )));
+        }
+    }
+
+    add_burned(storage, account, amount)?;
+    let burn_record = BurnRecord {
+        account: account.clone(),
+        amount,
+        reason: reason.clone(),
+        height: env.block.height,
+        timestamp: env.block.time.seconds(),
+    };
+    let id = append_burn_record(storage, &burn_record)?;
+
+    let mut attrs = vec![attr("burn_id", id.to_string())];
+    if let Some(reason) = reason {
+        attrs.push(attr("reason", reason));
+    }
+    Ok(attrs)
+}
+
+/// Appends a transfer or send with a reference to the recipient's payments.
+pub fn record_payment(
+    storage: &mut dyn Storage,
+    env: &Env,
+    sender: &Addr,
+    recipient: &Addr,
+    tax: &TransferTax,
+    reference: String,
+) -> Result<Vec<Attribute>, ContractError> {
+    if reference.is_empty() || reference.len() > MAX_PAYMENT_REFERENCE_LEN {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Invalid payment reference",
+        )));
+    }
+
+    let payment_record = PaymentRecord {
+        sender: sender.clone(),
+        recipient: recipient.clone(),
+        amount: tax.net_amount + tax.tax_amount,
+        net_amount: tax.net_amount,
+        reference: reference.clone(),
+        height: env.block.height,
+        timestamp: env.block.time.seconds(),
+    };
+    let id = append_payment_record(storage, &payment_record)?;
+
+    Ok(vec![
+        attr("payment_id", id.to_string()),
+        attr("reference", reference),
+    ])
+}
+
+pub fn mint_batch(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    mints: Vec<(String, Uint128)>,
+) -> Result<Response, ContractError> {
+    if mints.is_empty() {
+        return Err(ContractError::Std(StdError::generic_err("Empty batch")));
+    }
+    let total = mints
+        .iter()
+        .try_fold(Uint128::zero(), |total, (_, amount)| {
+            total.checked_add(*amount)
+        })?;
+    if !within_mint_approval_threshold(deps.storage, &env, &info.sender, total)? {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Mint above approval threshold",
+        )));
+    }
+
+    let mut res = Response::new();
+    let mut users = vec![];
+    for (recipient, amount) in mints {
+        let user = deps.api.addr_validate(&recipient)?;
+        let (leg_res, _) = mint_to(
+            deps.branch(),
+            env.clone(),
+            &info.sender,
+            &user,
+            amount,
+            None,
+        )?;
+        res = res.add_attributes(leg_res.attributes);
+        users.push(user);
+    }
+
+    after_balance_change(deps, env, res, users)
+}
+
+pub fn transfer_batch(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    transfers: Vec<(String, Uint128)>,
+) -> Result<Response, ContractError> {
+    if transfers.is_empty() {
+        return Err(ContractError::Std(StdError::generic_err("Empty batch")));
+    }
+    assert_not_frozen(deps.storage, &info.sender)?;
+
+    let mut res = Response::new();
+    let mut users = vec![info.sender.clone()];
+    let mut legs = vec![];
+    for (recipient, amount) in transfers {
+        let user = deps.api.addr_validate(&recipient)?;
+        legs.push((user.clone(), amount));
+        let tax = collect_transfer_tax(deps.storage, &info.sender, &user, amount)?;
+        let leg_res = execute_transfer(
+            deps.branch(),
+            env.clone(),
+            info.clone(),
+            recipient,
+            tax.net_amount,
+        )?;
+        res = res
+            .add_attributes(leg_res.attributes)
+            .add_attributes(transfer_tax_attrs(&tax));
+        users.push(user);
+        users.extend(tax.receiver);
+    }
+    assert_batch_launch_limits(deps.storage, &env, &info.sender, &legs)?;
+
+    after_balance_change(deps, env, res, users)
+}
+
+// checks the minter's quota, mints a single leg and appends it to the mint log,
+// the caller runs `after_balance_change`
+fn mint_to(
+    mut deps: DepsMut,
+    env: Env,
+    minter: &Addr,
+    user: &Addr,
+    amount: Uint128,
+    hook_contract: Option<Addr>,
+) -> Result<(Response, u64), ContractError> {
+    let mut minter_info = match read_minter_info(deps.storage, minter)? {
+        Some(minter_info) => minter_info,
+        None => return Err(ContractError::Unauthorized {}),
+    };
+
+    minter_info.minted = minter_info.minted.checked_add(amount)?;
+    if minter_info.minted > minter_info.quota {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Minter quota exceeded",
+        )));
+    }
+    store_minter_info(deps.storage, minter, &minter_info)?;
+
+    if !read_seilor_config(deps.storage)?.mint_to_frozen_enabled {
+        assert_not_frozen(deps.storage, user)?;
+    }
+
+    let sub_info = MessageInfo {
+        sender: env.contract.address.clone(),
+        funds: vec![],
+    };
+
+    let mint_record = MintRecord {
+        minter: minter.clone(),
+        recipient: user.clone(),
+        amount,
+        height: env.block.height,
+        timestamp: env.block.time.seconds(),
+        contract: hook_contract,
+    };
+    let id = append_mint_record(deps.storage, &mint_record)?;
+
+    let res = execute_mint(deps.branch(), env, sub_info, user.to_string(), amount)?;
+    Ok((res.add_attribute("mint_id", id.to_string()), id))
+}
+
+/// Runs the seilor bookkeeping for accounts whose balance has just been changed by cw20-base.
+pub fn after_balance_change(
+    deps: DepsMut,
+    env: Env,
+    res: Response,
+    users: Vec<Addr>,
+) -> Result<Response, ContractError> {
+    for user in &users {
+        assert_vesting_unlocked(deps.storage, &env, user)?;
+    }
+    checkpoint_balances(deps.storage, env.block.height, &users)?;
+    Ok(res.add_messages(refresh_reward_msgs(deps.storage, &env, users)?))
+}
+
+/// Checkpoints the current cw20 balance of each user and the total supply at `height`,
+/// moving the balance difference between the delegatees' voting power.
+pub fn checkpoint_balances(
+    storage: &mut dyn Storage,
+    height: u64,
+    users: &[Addr],
+) -> StdResult<()> {
+    for user in users {
+        let prev_balance = read_balance_snapshot(storage, user)?.unwrap_or_default();
+        let balance = BALANCES.may_load(storage, user)?.unwrap_or_default();
+        store_balance_snapshot(storage, user, &balance, height)?;
+        if balance != prev_balance {
+            if !prev_balance.is_zero() {
+                remove_holder_balance(storage, user, prev_balance);
+            }
+            if !balance.is_zero() {
+                store_holder_balance(storage, user, balance)?;
+            }
+        }
+
+        let delegatee = read_delegate(storage, user)?;
+        if balance > prev_balance {
+            move_votes(
+                storage,
+                height,
+                None,
+                delegatee.as_ref(),
+                balance - prev_balance,
+            )?;
+        } else if balance < prev_balance {
+            move_votes(
+                storage,
+                height,
+                delegatee.as_ref(),
+                None,
+                prev_balance - balance,
+            )?;
+        }
+    }
+    let total_supply = TOKEN_INFO.load(storage)?.total_supply;
+    store_total_supply_snapshot(storage, &total_supply, height)
+}
+
+const DEFAULT_SEED_LIMIT: u32 = 50;
+const MAX_SEED_LIMIT: u32 = 200;
+
+/// Seeds the next `limit` holders left over by a migrate. Checkpointing is incremental, so
+/// holders whose balance changed in between are seeded again without being counted twice.
+pub fn seed_holders(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let seilor_config = read_seilor_config(deps.storage)?;
+    if info.sender != seilor_config.gov {
+        return Err(ContractError::Unauthorized {});
+    }
+    let mut holder_seed = match read_holder_seed(deps.storage)? {
+        Some(holder_seed) => holder_seed,
+        None => {
+            return Err(ContractError::Std(StdError::generic_err(
+                "No holders left to seed",
+            )))
+        }
+    };
+
+    let limit = limit.unwrap_or(DEFAULT_SEED_LIMIT).min(MAX_SEED_LIMIT) as usize;
+    let start = holder_seed.start_after.as_ref().map(Bound::exclusive);
+    let holders = BALANCES
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<Addr>>>()?;
+    if holder_seed.checkpoint {
+        checkpoint_balances(deps.storage, env.block.height, &holders)?;
+    } else {
+        for holder in &holders {
+            let balance = BALANCES.load(deps.storage, holder)?;
+            if !balance.is_zero() {
+                store_holder_balance(deps.storage, holder, balance)?;
+            }
+        }
+    }
+
+    let done = holders.len() < limit;
+    if done {
+        remove_holder_seed(deps.storage);
+    } else {
+        holder_seed.start_after = holders.last().cloned();
+        store_holder_seed(deps.storage, &holder_seed)?;
+    }
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "seed_holders"),
+        attr("seeded", holders.len().to_string()),
+        attr("done", done.to_string()),
+    ]))
+}
+
+fn move_votes(
+    storage: &mut dyn Storage,
+    height: u64,
+    from: Option<&Addr>,
+    to: Option<&Addr>,
+    amount: Uint128,
+) -> StdResult<()> {
+    if from == to || amount.is_zero() {
+        return Ok(());
+    }
+    if let Some(from) = from {
+        let votes = read_votes(storage, from)?.checked_sub(amount)?;
+        store_votes(storage, from, &votes, height)?;
+    }
+    if let Some(to) = to {
+        let votes = read_votes(storage, to)?.checked_add(amount)?;
+        store_votes(storage, to, &votes, height)?;
+    }
+    Ok(())
+}
+
+pub fn delegate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    delegatee: String,
+) -> Result<Response, ContractError> {
+    let delegator = info.sender;
+    let delegatee = deps.api.addr_validate(&delegatee)?;
+
+    // a holder not seeded yet gets checkpointed first, or seeding would add its votes again
+    checkpoint_balances(
+        deps.storage,
+        env.block.height,
+        std::slice::from_ref(&delegator),
+    )?;
+    let prev_delegatee = read_delegate(deps.storage, &delegator)?;
+    let balance = BALANCES
+        .may_load(deps.storage, &delegator)?
+        .unwrap_or_default();
+    store_delegate(deps.storage, &delegator, &delegatee)?;
+    move_votes(
+        deps.storage,
+        env.block.height,
+        prev_delegatee.as_ref(),
+        Some(&delegatee),
+        balance,
+    )?;
+
+    let mut attrs = vec![
+        attr("action", "delegate"),
+        attr("delegator", delegator.to_string()),
+        attr("delegatee", delegatee.to_string()),
+    ];
+    if let Some(prev_delegatee) = prev_delegatee {
+        attrs.push(attr("prev_delegatee", prev_delegatee.to_string()));
+    }
+    Ok(Response::new().add_attributes(attrs))
+}
+
+/// Builds the fund RefreshReward hooks for the accounts whose balance has just changed.
+/// Returns no messages while the hook is disabled or the fund contract is not configured.
+pub fn refresh_reward_msgs(
+    storage: &dyn Storage,
+    env: &Env,
+    users: Vec<Addr>,
+) -> StdResult<Vec<CosmosMsg>> {
+    let seilor_config = read_seilor_config(storage)?;
+    if !seilor_config.refresh_reward_enabled || is_empty_str(seilor_config.fund.as_str()) {
+        return Ok(vec![]);
+    }
+
+    let mut refreshed: Vec<Addr> = vec![];
+    let mut msgs = vec![];
+    for user in users {
+        // the contract's own holdings (escrows, streams, wrapped tokens) earn no reward
+        if refreshed.contains(&user) || user == env.contract.address {
+            continue;
+        }
+        msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: seilor_config.fund.to_string(),
+            msg: to_binary(&SeilorFundMsg::RefreshReward { user: user.clone() })?,
+            funds: vec![],
+        }));
+        refreshed.push(user);
+    }
+    Ok(msgs)
 }
 
 // Burn has been modified to directly inherit the standard, and this modification will add gas to the VE module stacking. And complexity.
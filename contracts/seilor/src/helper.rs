@@ -1,3 +1,19 @@
+use bech32::{encode, ToBase32, Variant};
+use cosmwasm_std::{StdError, StdResult};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+
 pub fn is_empty_str(str: &str) -> bool {
     str.trim().is_empty()
 }
+
+/// Derives the bech32 account address of a compressed secp256k1 public key,
+/// i.e. ripemd160(sha256(pubkey)) encoded with the given prefix.
+pub fn pubkey_to_address(pubkey: &[u8], prefix: &str) -> StdResult<String> {
+    if pubkey.len() != 33 {
+        return Err(StdError::generic_err("Invalid pubkey length"));
+    }
+    let hash = Ripemd160::digest(Sha256::digest(pubkey));
+    encode(prefix, hash.to_base32(), Variant::Bech32)
+        .map_err(|err| StdError::generic_err(err.to_string()))
+}
@@ -1,10 +1,12 @@
 mod state;
 pub mod msg;
+pub mod error;
 mod handler;
 pub mod contract;
 mod querier;
 mod helper;
 mod mint_receiver;
+pub mod token_factory;
 
 
 #[cfg(test)]
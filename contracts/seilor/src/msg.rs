@@ -17,7 +17,130 @@ pub enum ExecuteMsg {
     UpdateConfig {
         fund: Option<Addr>,
         gov: Option<Addr>,
-        distribute: Option<Addr>,
+        refresh_reward_enabled: Option<bool>,
+        mint_to_frozen_enabled: Option<bool>,
+    },
+    /// Lowers max_supply and the cw20 minter cap, never below the current supply. Only gov.
+    LowerMaxSupply { max_supply: u128 },
+    /// Sets the amount a minter can mint per `window_seconds` before a `Mint` becomes a mint
+    /// proposal for gov to approve within `window_seconds`, none disables it. Only gov.
+    SetMintApproval {
+        threshold: Option<Uint128>,
+        window_seconds: u64,
+    },
+    /// Executes a pending mint proposal on behalf of its minter. Only gov.
+    ApproveMint { id: u64 },
+    /// Drops a pending mint proposal. Only gov, or anyone once it expired.
+    RejectMint { id: u64 },
+    /// Registers a minter or updates its lifetime quota. Only gov.
+    SetMinter { minter: String, quota: Uint128 },
+    /// Removes a minter from the registry. Only gov.
+    RemoveMinter { minter: String },
+    /// Delegates the sender's voting power to `delegatee` without moving tokens.
+    /// Delegate to yourself to vote with your own balance.
+    Delegate { delegatee: String },
+    /// Seeds the balance snapshots and the holders index for up to `limit` of the holders from
+    /// before a migrate, resuming where the last call stopped. Only gov.
+    SeedHolders { limit: Option<u32> },
+    /// Freezes an account so it can no longer send, transfer or burn tokens. Only gov.
+    Freeze { account: String },
+    /// Lifts the freeze on an account. Only gov.
+    Unfreeze { account: String },
+    /// Updates the transfer tax. `receiver` Some("") falls back to the fund. Only gov.
+    UpdateTransferTax {
+        tax_bps: Option<u64>,
+        burn_bps: Option<u64>,
+        receiver: Option<String>,
+    },
+    /// Adds or removes an account from the transfer tax exemption list. Only gov.
+    SetTaxExempt { account: String, exempt: bool },
+    /// Sets the launch-phase limits on Transfer, Send, TransferFrom, SendFrom, the reference
+    /// variants, TransferBatch, TransferLocked and CreateStream: a maximum amount per transfer, a
+    /// maximum recipient balance and a cooldown in seconds between two outgoing transfers of an
+    /// address (0 disables it). Payouts by this contract are held to the maximum balance. Fails
+    /// once the limits are lifted. Only gov.
+    SetLaunchLimits {
+        max_transfer_amount: Option<Uint128>,
+        max_wallet_balance: Option<Uint128>,
+        transfer_cooldown_seconds: u64,
+    },
+    /// Removes the launch limits for good. Only gov.
+    LiftLaunchLimits {},
+    /// Adds or removes an account from the launch limit exemption list. Only gov.
+    SetLimitExempt { account: String, exempt: bool },
+    /// Moves `amount` into an escrow held by this contract, claimable by `recipient` once the block
+    /// time reaches `release_at` (seconds). A cancelable escrow can be taken back by the sender before that.
+    TransferLocked {
+        recipient: String,
+        amount: Uint128,
+        release_at: u64,
+        cancelable: bool,
+    },
+    /// Pays out a released escrow to its recipient. Only the recipient.
+    ClaimLocked { id: u64 },
+    /// Returns a cancelable escrow to its sender before release. Only the sender.
+    CancelLocked { id: u64 },
+    /// Locks `amount` and streams it linearly to `recipient` between the `start` and `end`
+    /// block times (seconds).
+    CreateStream {
+        recipient: String,
+        amount: Uint128,
+        start: u64,
+        end: u64,
+    },
+    /// Withdraws everything accrued so far. Only the recipient.
+    WithdrawStream { id: u64 },
+    /// Closes a stream, paying the accrued part to the recipient and the rest back to the sender.
+    /// Only the sender or the recipient.
+    CancelStream { id: u64 },
+    /// Sends native coins or foreign cw20 tokens held by this contract to `recipient`.
+    /// SEILOR itself can't be recovered since it is held for escrows, streams and the wrap
+    /// denom, and neither can the wrap denom. Only gov.
+    RecoverTokens {
+        asset: RecoverAsset,
+        recipient: String,
+        amount: Uint128,
+    },
+    /// Gives `recipient` a balance of `amount` that can't be moved until it unlocks along `schedule`.
+    /// A minter mints the amount against its quota, gov transfers it from its own balance.
+    /// An account holds at most one vesting that isn't fully vested. Only gov or a minter.
+    CreateVesting {
+        recipient: String,
+        amount: Uint128,
+        schedule: VestingSchedule,
+    },
+    /// Sets the emission curve and the receivers its emission is split between by weight.
+    /// What accrued under the previous schedule is emitted first. Only gov.
+    SetEmissionSchedule {
+        curve: EmissionCurve,
+        receivers: Vec<EmissionReceiver>,
+    },
+    /// Mints what the emission schedule accrued since the last emit to its receivers,
+    /// capped at max_supply. Anyone can call this.
+    Emit {},
+    /// Claims the minted tokens held for the sender because their mint hook failed.
+    ClaimMintEscrow {},
+    /// Creates the token-factory denom `factory/{contract}/{subdenom}` SEILOR is wrapped into.
+    /// Can only be done once. Only gov.
+    CreateWrapDenom { subdenom: String },
+    /// Locks `amount` of the sender's SEILOR in the contract and mints as much of the wrap denom
+    /// to the sender.
+    Wrap { amount: Uint128 },
+    /// Burns the wrap denom sent along and releases as much SEILOR to the sender, less the
+    /// transfer tax. Held to the sender's launch limits like a transfer to itself.
+    Unwrap {},
+    /// Adds or removes an account from the circulating supply exclusion list. Only gov.
+    SetSupplyExcluded { account: String, excluded: bool },
+    /// Increases the allowance granted by `owner` to `spender` with a `PermitMsg` signed off-chain
+    /// by the owner's secp256k1 key. Anyone can submit it, `nonce` must be the owner's next nonce.
+    Permit {
+        owner: String,
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+        nonce: u64,
+        signature: Binary,
+        pubkey: Binary,
     },
     Mint {
         recipient: String,
@@ -25,12 +148,24 @@ pub enum ExecuteMsg {
         contract: Option<String>,
         msg: Option<Binary>,
     },
-    /// Burn is a base message to destroy tokens forever
-    Burn { amount: Uint128 },
+    /// Mints to several recipients at once, each leg is checked like `Mint`.
+    /// Fails if the batch total takes the minter above the mint approval threshold.
+    MintBatch { mints: Vec<(String, Uint128)> },
+    /// Burn is a base message to destroy tokens forever. The optional reason is kept in the burn log
+    Burn {
+        amount: Uint128,
+        reason: Option<String>,
+    },
     /// Only with "approval" extension. Destroys tokens forever
-    BurnFrom { owner: String, amount: Uint128 },
+    BurnFrom {
+        owner: String,
+        amount: Uint128,
+        reason: Option<String>,
+    },
     /// Implements CW20. Transfer is a base message to move tokens to another account without triggering actions
     Transfer { recipient: String, amount: Uint128 },
+    /// Transfers to several recipients at once, failing as a whole if any leg fails
+    TransferBatch { transfers: Vec<(String, Uint128)> },
     /// Implements CW20.  Send is a base message to transfer tokens to a contract and trigger an action
     /// on the receiving contract.
     Send {
@@ -38,6 +173,19 @@ pub enum ExecuteMsg {
         amount: Uint128,
         msg: Binary,
     },
+    /// Transfer that is listed under `reference` in the recipient's `PaymentsReceived`.
+    TransferWithReference {
+        recipient: String,
+        amount: Uint128,
+        reference: String,
+    },
+    /// Send that is listed under `reference` in the contract's `PaymentsReceived`.
+    SendWithReference {
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+        reference: String,
+    },
     /// Implements CW20 "approval" extension. Allows spender to access an additional amount tokens
     /// from the owner's (env.sender) account. If expires is Some(), overwrites current allowance
     /// expiration with this one.
@@ -54,6 +202,22 @@ pub enum ExecuteMsg {
         amount: Uint128,
         expires: Option<Expiration>,
     },
+    /// Allows spender to spend up to `amount_per_period` of the owner's (env.sender) tokens every
+    /// `period_seconds` through TransferFrom, SendFrom and BurnFrom. Adds to the per-period amount
+    /// of an existing periodic allowance and overwrites its expiration, a new period length starts
+    /// a new period. Fails while the spender has a cw20 allowance, and IncreaseAllowance and
+    /// Permit fail while it has a periodic one.
+    IncreasePeriodicAllowance {
+        spender: String,
+        amount_per_period: Uint128,
+        period_seconds: u64,
+        expires: Option<Expiration>,
+    },
+    /// Lowers the per-period amount of a periodic allowance, removing it once it reaches zero.
+    DecreasePeriodicAllowance {
+        spender: String,
+        amount_per_period: Uint128,
+    },
     /// Implements CW20 "approval" extension. Transfers amount tokens from owner -> recipient
     /// if `env.sender` has sufficient pre-approval.
     TransferFrom {
@@ -89,11 +253,222 @@ pub enum ExecuteMsg {
     UploadLogo(Logo),
 }
 
+#[cw_serde]
+pub enum VestingSchedule {
+    /// Unlocks linearly from `start` to `end`, block times in seconds.
+    Linear { start: u64, end: u64 },
+    /// Unlocks all at once at `release_at`, block time in seconds.
+    Cliff { release_at: u64 },
+}
+
+#[cw_serde]
+pub struct EmissionSegment {
+    // block time in seconds the rate starts at
+    pub start: u64,
+    pub rate_per_second: Uint128,
+}
+
+#[cw_serde]
+pub enum EmissionCurve {
+    /// Each segment's rate runs until the next segment starts, the last one never ends.
+    Piecewise { segments: Vec<EmissionSegment> },
+    /// `initial_rate` per second from `start`, halved every `epoch_seconds`.
+    Halving {
+        start: u64,
+        initial_rate: Uint128,
+        epoch_seconds: u64,
+    },
+}
+
+#[cw_serde]
+pub struct EmissionReceiver {
+    pub address: String,
+    pub weight: u64,
+}
+
+#[cw_serde]
+pub enum RecoverAsset {
+    Native { denom: String },
+    Cw20 { contract_addr: String },
+}
+
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
     #[returns(SeilorConfigResponse)]
     SeilorConfig {},
+    /// Returns the registered minters with their quota and remaining allowance. Supports pagination.
+    #[returns(MintersResponse)]
+    Minters {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Returns the balance of the given address at the start of block `height`.
+    #[returns(cw20::BalanceResponse)]
+    BalanceAt { address: String, height: u64 },
+    /// Returns the total supply at the start of block `height`.
+    #[returns(TotalSupplyAtResponse)]
+    TotalSupplyAt { height: u64 },
+
+    /// Returns the current voting power delegated to the given address.
+    #[returns(VotesResponse)]
+    Votes { address: String },
+    /// Returns the voting power delegated to the given address at the start of block `height`.
+    #[returns(VotesResponse)]
+    VotesAt { address: String, height: u64 },
+    /// Returns who the given delegator has delegated its voting power to.
+    #[returns(DelegationResponse)]
+    Delegation { delegator: String },
+    /// Returns the accounts frozen by gov. Supports pagination.
+    #[returns(FrozenAccountsResponse)]
+    FrozenAccounts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the transfer tax settings with the effective tax receiver.
+    #[returns(TransferTaxResponse)]
+    TransferTax {},
+    /// Returns the accounts exempt from the transfer tax. Supports pagination.
+    #[returns(TaxExemptAccountsResponse)]
+    TaxExemptAccounts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the launch limits.
+    #[returns(LaunchLimitsResponse)]
+    LaunchLimits {},
+    /// Returns the accounts exempt from the launch limits, besides gov and the fund. Supports pagination.
+    #[returns(LimitExemptAccountsResponse)]
+    LimitExemptAccounts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the tax charged on a transfer of `amount` from `sender` to `recipient`
+    /// and the net amount the recipient would get.
+    #[returns(SimulateTransferResponse)]
+    SimulateTransfer {
+        sender: String,
+        recipient: String,
+        amount: Uint128,
+    },
+    /// Returns the total supply minus the balances of the excluded accounts,
+    /// and the amount that can still be minted under max_supply.
+    #[returns(CirculatingSupplyResponse)]
+    CirculatingSupply {},
+    /// Returns the wrap denom and the amount of SEILOR locked for it.
+    #[returns(WrappedSupplyResponse)]
+    WrappedSupply {},
+    /// Returns the accounts excluded from the circulating supply. Supports pagination.
+    #[returns(SupplyExcludedAccountsResponse)]
+    SupplyExcludedAccounts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the holders with the largest balances first. Supports pagination,
+    /// `start_after` is the last address of the previous page.
+    #[returns(TopHoldersResponse)]
+    TopHolders {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns a pending escrow.
+    #[returns(EscrowResponse)]
+    Escrow { id: u64 },
+    /// Returns the pending escrows created by `sender`. Supports pagination.
+    #[returns(EscrowsResponse)]
+    EscrowsBySender {
+        sender: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the pending escrows payable to `recipient`. Supports pagination.
+    #[returns(EscrowsResponse)]
+    EscrowsByRecipient {
+        recipient: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns an open stream with the amount the recipient can withdraw now.
+    #[returns(StreamResponse)]
+    Stream { id: u64 },
+    /// Returns the open streams paid by `sender`. Supports pagination.
+    #[returns(StreamsResponse)]
+    StreamsBySender {
+        sender: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the open streams paid to `recipient`. Supports pagination.
+    #[returns(StreamsResponse)]
+    StreamsByRecipient {
+        recipient: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the mint log, oldest first, optionally only the mints of `minter`. Supports pagination.
+    #[returns(MintHistoryResponse)]
+    MintHistory {
+        minter: Option<String>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the mint approval threshold and window.
+    #[returns(MintApprovalResponse)]
+    MintApproval {},
+    /// Returns a pending mint proposal.
+    #[returns(MintProposalResponse)]
+    MintProposal { id: u64 },
+    /// Returns the pending mint proposals, oldest first, expired ones included. Supports pagination.
+    #[returns(MintProposalsResponse)]
+    MintProposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the minted tokens held for `address` because their mint hook failed.
+    #[returns(MintEscrowResponse)]
+    MintEscrow { address: String },
+    /// Returns the failed mint hooks by mint id, optionally only those of `recipient`.
+    /// Supports pagination.
+    #[returns(MintHookFailuresResponse)]
+    MintHookFailures {
+        recipient: Option<String>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the cumulative amount burned, and the amount burned from `address` if given.
+    #[returns(BurnStatsResponse)]
+    BurnStats { address: Option<String> },
+    /// Returns the burn log, oldest first. Supports pagination.
+    #[returns(BurnHistoryResponse)]
+    BurnHistory {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the payments with a reference received by `recipient`, oldest first,
+    /// optionally only those with `reference`. Supports pagination.
+    #[returns(PaymentsResponse)]
+    PaymentsReceived {
+        recipient: String,
+        reference: Option<String>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns the emission schedule and what it has accrued so far, none if not set.
+    #[returns(Option<EmissionScheduleResponse>)]
+    EmissionSchedule {},
+    /// Returns the supply at a future block time if only the emission schedule mints until then.
+    #[returns(ProjectedSupplyResponse)]
+    ProjectedSupply { at: u64 },
+    /// Returns the locked, vested and spendable amounts of an address.
+    #[returns(VestingBalanceResponse)]
+    VestingBalance { address: String },
+    /// Returns the periodic allowance of spender over owner's tokens, refilled as of now.
+    #[returns(PeriodicAllowanceResponse)]
+    PeriodicAllowance { owner: String, spender: String },
+    /// Returns the nonce the owner's next permit must be signed with.
+    #[returns(PermitNonceResponse)]
+    PermitNonce { owner: String },
 
     /// Returns the current balance of the given address, 0 if unset.
     #[returns(cw20::BalanceResponse)]
@@ -153,9 +528,305 @@ pub enum SeilorFundMsg {
 pub struct SeilorConfigResponse {
     pub max_supply: u128,
     pub fund: Addr,
-    pub distribute: Addr,
     pub gov: Addr,
+    pub refresh_reward_enabled: bool,
+    pub mint_to_frozen_enabled: bool,
+    // what counts against max_supply, lowered by burns
+    pub total_supply: Uint128,
+    pub remaining: Uint128,
 }
 
 #[cw_serde]
-pub struct MigrateMsg {}
+pub struct MinterInfoResponse {
+    pub minter: Addr,
+    pub quota: Uint128,
+    pub minted: Uint128,
+    pub remaining: Uint128,
+}
+
+#[cw_serde]
+pub struct MintersResponse {
+    pub minters: Vec<MinterInfoResponse>,
+}
+
+#[cw_serde]
+pub struct TotalSupplyAtResponse {
+    pub total_supply: Uint128,
+}
+
+#[cw_serde]
+pub struct VotesResponse {
+    pub votes: Uint128,
+}
+
+#[cw_serde]
+pub struct DelegationResponse {
+    pub delegatee: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct FrozenAccountsResponse {
+    pub accounts: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct TransferTaxResponse {
+    pub tax_bps: u64,
+    pub burn_bps: u64,
+    pub receiver: Addr,
+}
+
+#[cw_serde]
+pub struct TaxExemptAccountsResponse {
+    pub accounts: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct LaunchLimitsResponse {
+    pub max_transfer_amount: Option<Uint128>,
+    pub max_wallet_balance: Option<Uint128>,
+    pub transfer_cooldown_seconds: u64,
+    pub lifted: bool,
+}
+
+#[cw_serde]
+pub struct LimitExemptAccountsResponse {
+    pub accounts: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct SimulateTransferResponse {
+    pub amount: Uint128,
+    pub tax_amount: Uint128,
+    pub burn_amount: Uint128,
+    pub receiver_amount: Uint128,
+    pub net_amount: Uint128,
+}
+
+#[cw_serde]
+pub struct EmissionScheduleResponse {
+    pub curve: EmissionCurve,
+    pub receivers: Vec<EmissionReceiver>,
+    // block time emission has been minted up to
+    pub last_emitted: u64,
+    // accrued since last_emitted, before the max_supply cap
+    pub pending: Uint128,
+}
+
+#[cw_serde]
+pub struct ProjectedSupplyResponse {
+    pub at: u64,
+    pub supply: Uint128,
+}
+
+#[cw_serde]
+pub struct VestingBalanceResponse {
+    pub balance: Uint128,
+    // vesting amount not unlocked yet
+    pub locked: Uint128,
+    // vesting amount unlocked so far
+    pub vested: Uint128,
+    // balance that can be moved
+    pub spendable: Uint128,
+    pub schedule: Option<VestingSchedule>,
+}
+
+#[cw_serde]
+pub struct PeriodicAllowanceResponse {
+    pub amount_per_period: Uint128,
+    pub period_seconds: u64,
+    pub period_start: u64,
+    pub spent: Uint128,
+    // left to spend in the current period
+    pub available: Uint128,
+    pub expires: Expiration,
+}
+
+#[cw_serde]
+pub struct WrappedSupplyResponse {
+    pub denom: Option<String>,
+    pub wrapped_supply: Uint128,
+}
+
+#[cw_serde]
+pub struct CirculatingSupplyResponse {
+    pub total_supply: Uint128,
+    pub excluded_supply: Uint128,
+    pub circulating_supply: Uint128,
+    pub max_supply: Uint128,
+    pub mintable: Uint128,
+}
+
+#[cw_serde]
+pub struct SupplyExcludedAccountsResponse {
+    pub accounts: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct HolderResponse {
+    pub address: Addr,
+    pub balance: Uint128,
+}
+
+#[cw_serde]
+pub struct TopHoldersResponse {
+    pub holders: Vec<HolderResponse>,
+}
+
+#[cw_serde]
+pub struct EscrowResponse {
+    pub id: u64,
+    pub sender: Addr,
+    pub recipient: Addr,
+    pub amount: Uint128,
+    pub release_at: u64,
+    pub cancelable: bool,
+}
+
+#[cw_serde]
+pub struct EscrowsResponse {
+    pub escrows: Vec<EscrowResponse>,
+}
+
+#[cw_serde]
+pub struct StreamResponse {
+    pub id: u64,
+    pub sender: Addr,
+    pub recipient: Addr,
+    pub amount: Uint128,
+    pub withdrawn: Uint128,
+    pub withdrawable: Uint128,
+    pub start: u64,
+    pub end: u64,
+}
+
+#[cw_serde]
+pub struct StreamsResponse {
+    pub streams: Vec<StreamResponse>,
+}
+
+#[cw_serde]
+pub struct MintRecordResponse {
+    pub id: u64,
+    pub minter: Addr,
+    pub recipient: Addr,
+    pub amount: Uint128,
+    pub height: u64,
+    pub timestamp: u64,
+    pub contract: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct MintApprovalResponse {
+    pub threshold: Option<Uint128>,
+    pub window_seconds: u64,
+}
+
+#[cw_serde]
+pub struct MintProposalResponse {
+    pub id: u64,
+    pub minter: Addr,
+    pub recipient: Addr,
+    pub amount: Uint128,
+    pub contract: Option<Addr>,
+    pub msg: Option<Binary>,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub expired: bool,
+}
+
+#[cw_serde]
+pub struct MintProposalsResponse {
+    pub proposals: Vec<MintProposalResponse>,
+}
+
+#[cw_serde]
+pub struct MintHistoryResponse {
+    pub mints: Vec<MintRecordResponse>,
+}
+
+#[cw_serde]
+pub struct MintEscrowResponse {
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct MintHookFailureResponse {
+    pub mint_id: u64,
+    pub minter: Addr,
+    pub recipient: Addr,
+    pub contract: Addr,
+    pub amount: Uint128,
+    pub error: String,
+    pub height: u64,
+    pub timestamp: u64,
+}
+
+#[cw_serde]
+pub struct MintHookFailuresResponse {
+    pub failures: Vec<MintHookFailureResponse>,
+}
+
+#[cw_serde]
+pub struct BurnStatsResponse {
+    pub total_burned: Uint128,
+    pub burned: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct BurnRecordResponse {
+    pub id: u64,
+    pub account: Addr,
+    pub amount: Uint128,
+    pub reason: Option<String>,
+    pub height: u64,
+    pub timestamp: u64,
+}
+
+#[cw_serde]
+pub struct PaymentResponse {
+    pub id: u64,
+    pub sender: Addr,
+    pub recipient: Addr,
+    // amount sent
+    pub amount: Uint128,
+    // amount received after the transfer tax
+    pub net_amount: Uint128,
+    pub reference: String,
+    pub height: u64,
+    pub timestamp: u64,
+}
+
+#[cw_serde]
+pub struct PaymentsResponse {
+    pub payments: Vec<PaymentResponse>,
+}
+
+#[cw_serde]
+pub struct BurnHistoryResponse {
+    pub burns: Vec<BurnRecordResponse>,
+}
+
+/// The document signed for `ExecuteMsg::Permit`. The signature is over sha256 of its JSON encoding.
+#[cw_serde]
+pub struct PermitMsg {
+    pub chain_id: String,
+    pub contract: String,
+    pub owner: String,
+    pub spender: String,
+    pub amount: Uint128,
+    pub expires: Option<Expiration>,
+    pub nonce: u64,
+}
+
+#[cw_serde]
+pub struct PermitNonceResponse {
+    pub nonce: u64,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {
+    // quotas for the legacy fund/distribute minters, default max_supply
+    pub fund_quota: Option<Uint128>,
+    pub distribute_quota: Option<Uint128>,
+}
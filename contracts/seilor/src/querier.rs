@@ -1,13 +1,622 @@
-use crate::msg::SeilorConfigResponse;
-use crate::state::{read_seilor_config, SeilorConfig};
-use cosmwasm_std::{Deps, StdResult};
+use crate::handler::{
+    compute_transfer_tax, emission_accrued, refill_periodic_allowance, stream_withdrawable,
+    transfer_tax_receiver, vesting_locked,
+};
+use crate::msg::{
+    BurnHistoryResponse, BurnRecordResponse, BurnStatsResponse, CirculatingSupplyResponse,
+    DelegationResponse, EmissionReceiver, EmissionScheduleResponse, EscrowResponse,
+    EscrowsResponse, FrozenAccountsResponse, HolderResponse, LaunchLimitsResponse,
+    LimitExemptAccountsResponse, MintApprovalResponse, MintEscrowResponse, MintHistoryResponse,
+    MintHookFailureResponse, MintHookFailuresResponse, MintProposalResponse, MintProposalsResponse,
+    MintRecordResponse, MinterInfoResponse, MintersResponse, PaymentResponse, PaymentsResponse,
+    PeriodicAllowanceResponse, PermitNonceResponse, ProjectedSupplyResponse, SeilorConfigResponse,
+    SimulateTransferResponse, StreamResponse, StreamsResponse, SupplyExcludedAccountsResponse,
+    TaxExemptAccountsResponse, TopHoldersResponse, TotalSupplyAtResponse, TransferTaxResponse,
+    VestingBalanceResponse, VotesResponse, WrappedSupplyResponse,
+};
+use crate::state::{
+    read_all_supply_excluded_accounts, read_balance_snapshot_at, read_burn_records, read_burned,
+    read_delegate, read_emission_config, read_escrow, read_escrows_by_recipient,
+    read_escrows_by_sender, read_frozen_accounts, read_launch_limits, read_limit_exempt_accounts,
+    read_mint_approval_config, read_mint_escrow, read_mint_hook_failures,
+    read_mint_hook_failures_by_recipient, read_mint_proposal, read_mint_proposals,
+    read_mint_records, read_mint_records_by_minter, read_minter_infos, read_payments_received,
+    read_periodic_allowance, read_permit_nonce, read_seilor_config, read_stream,
+    read_streams_by_recipient, read_streams_by_sender, read_supply_excluded_accounts,
+    read_tax_exempt_accounts, read_top_holders, read_total_burned, read_total_supply_snapshot_at,
+    read_transfer_tax_config, read_vesting_account, read_votes, read_votes_at, read_wrap_denom,
+    read_wrapped_supply, Escrow, MintProposal, SeilorConfig, Stream,
+};
+use cosmwasm_std::{Deps, Env, StdError, StdResult, Uint128};
+use cw20::BalanceResponse;
+use cw20_base::state::{BALANCES, TOKEN_INFO};
+use cw_utils::Expiration;
 
 pub fn query_seilor_config(deps: Deps) -> StdResult<SeilorConfigResponse> {
     let config: SeilorConfig = read_seilor_config(deps.storage)?;
+    let total_supply = TOKEN_INFO.load(deps.storage)?.total_supply;
     Ok(SeilorConfigResponse {
         max_supply: config.max_supply,
         fund: config.fund,
-        distribute: config.distribute,
         gov: config.gov,
+        refresh_reward_enabled: config.refresh_reward_enabled,
+        mint_to_frozen_enabled: config.mint_to_frozen_enabled,
+        total_supply,
+        remaining: Uint128::from(config.max_supply).saturating_sub(total_supply),
     })
 }
+
+pub fn query_minters(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<MintersResponse> {
+    let start_after = match start_after {
+        Some(start_after) => Some(deps.api.addr_validate(&start_after)?),
+        None => None,
+    };
+    let minters = read_minter_infos(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|(minter, minter_info)| MinterInfoResponse {
+            minter,
+            quota: minter_info.quota,
+            minted: minter_info.minted,
+            remaining: minter_info.quota.saturating_sub(minter_info.minted),
+        })
+        .collect();
+    Ok(MintersResponse { minters })
+}
+
+pub fn query_balance_at(deps: Deps, address: String, height: u64) -> StdResult<BalanceResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let balance = read_balance_snapshot_at(deps.storage, &address, height)?.unwrap_or_default();
+    Ok(BalanceResponse { balance })
+}
+
+pub fn query_total_supply_at(deps: Deps, height: u64) -> StdResult<TotalSupplyAtResponse> {
+    let total_supply = read_total_supply_snapshot_at(deps.storage, height)?.unwrap_or_default();
+    Ok(TotalSupplyAtResponse { total_supply })
+}
+
+pub fn query_votes(deps: Deps, address: String) -> StdResult<VotesResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let votes = read_votes(deps.storage, &address)?;
+    Ok(VotesResponse { votes })
+}
+
+pub fn query_votes_at(deps: Deps, address: String, height: u64) -> StdResult<VotesResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let votes = read_votes_at(deps.storage, &address, height)?;
+    Ok(VotesResponse { votes })
+}
+
+pub fn query_delegation(deps: Deps, delegator: String) -> StdResult<DelegationResponse> {
+    let delegator = deps.api.addr_validate(&delegator)?;
+    let delegatee = read_delegate(deps.storage, &delegator)?;
+    Ok(DelegationResponse { delegatee })
+}
+
+pub fn query_frozen_accounts(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<FrozenAccountsResponse> {
+    let start_after = match start_after {
+        Some(start_after) => Some(deps.api.addr_validate(&start_after)?),
+        None => None,
+    };
+    let accounts = read_frozen_accounts(deps.storage, start_after, limit)?;
+    Ok(FrozenAccountsResponse { accounts })
+}
+
+pub fn query_transfer_tax(deps: Deps) -> StdResult<TransferTaxResponse> {
+    let tax_config = read_transfer_tax_config(deps.storage)?;
+    Ok(TransferTaxResponse {
+        tax_bps: tax_config.tax_bps,
+        burn_bps: tax_config.burn_bps,
+        receiver: transfer_tax_receiver(deps.storage)?,
+    })
+}
+
+pub fn query_tax_exempt_accounts(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TaxExemptAccountsResponse> {
+    let start_after = match start_after {
+        Some(start_after) => Some(deps.api.addr_validate(&start_after)?),
+        None => None,
+    };
+    let accounts = read_tax_exempt_accounts(deps.storage, start_after, limit)?;
+    Ok(TaxExemptAccountsResponse { accounts })
+}
+
+pub fn query_simulate_transfer(
+    deps: Deps,
+    sender: String,
+    recipient: String,
+    amount: Uint128,
+) -> StdResult<SimulateTransferResponse> {
+    let sender = deps.api.addr_validate(&sender)?;
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let tax = compute_transfer_tax(deps.storage, &sender, &recipient, amount)?;
+    Ok(SimulateTransferResponse {
+        amount,
+        tax_amount: tax.tax_amount,
+        burn_amount: tax.burn_amount,
+        receiver_amount: tax.receiver_amount,
+        net_amount: tax.net_amount,
+    })
+}
+
+pub fn query_permit_nonce(deps: Deps, owner: String) -> StdResult<PermitNonceResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let nonce = read_permit_nonce(deps.storage, &owner)?;
+    Ok(PermitNonceResponse { nonce })
+}
+
+pub fn query_burn_stats(deps: Deps, address: Option<String>) -> StdResult<BurnStatsResponse> {
+    let burned = match address {
+        Some(address) => {
+            let address = deps.api.addr_validate(&address)?;
+            Some(read_burned(deps.storage, &address)?)
+        }
+        None => None,
+    };
+    Ok(BurnStatsResponse {
+        total_burned: read_total_burned(deps.storage)?,
+        burned,
+    })
+}
+
+pub fn query_burn_history(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<BurnHistoryResponse> {
+    let burns = read_burn_records(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|(id, burn_record)| BurnRecordResponse {
+            id,
+            account: burn_record.account,
+            amount: burn_record.amount,
+            reason: burn_record.reason,
+            height: burn_record.height,
+            timestamp: burn_record.timestamp,
+        })
+        .collect();
+    Ok(BurnHistoryResponse { burns })
+}
+
+pub fn query_circulating_supply(deps: Deps) -> StdResult<CirculatingSupplyResponse> {
+    let total_supply = TOKEN_INFO.load(deps.storage)?.total_supply;
+    let max_supply = Uint128::from(read_seilor_config(deps.storage)?.max_supply);
+
+    let mut excluded_supply = Uint128::zero();
+    for account in read_all_supply_excluded_accounts(deps.storage)? {
+        excluded_supply += BALANCES
+            .may_load(deps.storage, &account)?
+            .unwrap_or_default();
+    }
+
+    Ok(CirculatingSupplyResponse {
+        total_supply,
+        excluded_supply,
+        circulating_supply: total_supply.saturating_sub(excluded_supply),
+        max_supply,
+        mintable: max_supply.saturating_sub(total_supply),
+    })
+}
+
+pub fn query_supply_excluded_accounts(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<SupplyExcludedAccountsResponse> {
+    let start_after = match start_after {
+        Some(start_after) => Some(deps.api.addr_validate(&start_after)?),
+        None => None,
+    };
+    let accounts = read_supply_excluded_accounts(deps.storage, start_after, limit)?;
+    Ok(SupplyExcludedAccountsResponse { accounts })
+}
+
+pub fn query_top_holders(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TopHoldersResponse> {
+    let start_after = match start_after {
+        Some(start_after) => {
+            let holder = deps.api.addr_validate(&start_after)?;
+            let balance = BALANCES
+                .may_load(deps.storage, &holder)?
+                .unwrap_or_default();
+            Some((balance, holder))
+        }
+        None => None,
+    };
+    let holders = read_top_holders(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|(address, balance)| HolderResponse { address, balance })
+        .collect();
+    Ok(TopHoldersResponse { holders })
+}
+
+fn escrow_response(id: u64, escrow: Escrow) -> EscrowResponse {
+    EscrowResponse {
+        id,
+        sender: escrow.sender,
+        recipient: escrow.recipient,
+        amount: escrow.amount,
+        release_at: escrow.release_at,
+        cancelable: escrow.cancelable,
+    }
+}
+
+pub fn query_escrow(deps: Deps, id: u64) -> StdResult<EscrowResponse> {
+    match read_escrow(deps.storage, id)? {
+        Some(escrow) => Ok(escrow_response(id, escrow)),
+        None => Err(StdError::generic_err("Escrow not found")),
+    }
+}
+
+pub fn query_escrows_by_sender(
+    deps: Deps,
+    sender: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<EscrowsResponse> {
+    let sender = deps.api.addr_validate(&sender)?;
+    let escrows = read_escrows_by_sender(deps.storage, &sender, start_after, limit)?
+        .into_iter()
+        .map(|(id, escrow)| escrow_response(id, escrow))
+        .collect();
+    Ok(EscrowsResponse { escrows })
+}
+
+pub fn query_escrows_by_recipient(
+    deps: Deps,
+    recipient: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<EscrowsResponse> {
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let escrows = read_escrows_by_recipient(deps.storage, &recipient, start_after, limit)?
+        .into_iter()
+        .map(|(id, escrow)| escrow_response(id, escrow))
+        .collect();
+    Ok(EscrowsResponse { escrows })
+}
+
+fn stream_response(env: &Env, id: u64, stream: Stream) -> StreamResponse {
+    StreamResponse {
+        id,
+        withdrawable: stream_withdrawable(&stream, env.block.time.seconds()),
+        sender: stream.sender,
+        recipient: stream.recipient,
+        amount: stream.amount,
+        withdrawn: stream.withdrawn,
+        start: stream.start,
+        end: stream.end,
+    }
+}
+
+pub fn query_stream(deps: Deps, env: Env, id: u64) -> StdResult<StreamResponse> {
+    match read_stream(deps.storage, id)? {
+        Some(stream) => Ok(stream_response(&env, id, stream)),
+        None => Err(StdError::generic_err("Stream not found")),
+    }
+}
+
+pub fn query_streams_by_sender(
+    deps: Deps,
+    env: Env,
+    sender: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<StreamsResponse> {
+    let sender = deps.api.addr_validate(&sender)?;
+    let streams = read_streams_by_sender(deps.storage, &sender, start_after, limit)?
+        .into_iter()
+        .map(|(id, stream)| stream_response(&env, id, stream))
+        .collect();
+    Ok(StreamsResponse { streams })
+}
+
+pub fn query_streams_by_recipient(
+    deps: Deps,
+    env: Env,
+    recipient: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<StreamsResponse> {
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let streams = read_streams_by_recipient(deps.storage, &recipient, start_after, limit)?
+        .into_iter()
+        .map(|(id, stream)| stream_response(&env, id, stream))
+        .collect();
+    Ok(StreamsResponse { streams })
+}
+
+pub fn query_mint_history(
+    deps: Deps,
+    minter: Option<String>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<MintHistoryResponse> {
+    let mint_records = match minter {
+        Some(minter) => {
+            let minter = deps.api.addr_validate(&minter)?;
+            read_mint_records_by_minter(deps.storage, &minter, start_after, limit)?
+        }
+        None => read_mint_records(deps.storage, start_after, limit)?,
+    };
+    let mints = mint_records
+        .into_iter()
+        .map(|(id, mint_record)| MintRecordResponse {
+            id,
+            minter: mint_record.minter,
+            recipient: mint_record.recipient,
+            amount: mint_record.amount,
+            height: mint_record.height,
+            timestamp: mint_record.timestamp,
+            contract: mint_record.contract,
+        })
+        .collect();
+    Ok(MintHistoryResponse { mints })
+}
+
+pub fn query_wrapped_supply(deps: Deps) -> StdResult<WrappedSupplyResponse> {
+    Ok(WrappedSupplyResponse {
+        denom: read_wrap_denom(deps.storage)?,
+        wrapped_supply: read_wrapped_supply(deps.storage)?,
+    })
+}
+
+pub fn query_mint_escrow(deps: Deps, address: String) -> StdResult<MintEscrowResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    Ok(MintEscrowResponse {
+        amount: read_mint_escrow(deps.storage, &address)?,
+    })
+}
+
+pub fn query_mint_hook_failures(
+    deps: Deps,
+    recipient: Option<String>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<MintHookFailuresResponse> {
+    let mint_hook_failures = match recipient {
+        Some(recipient) => {
+            let recipient = deps.api.addr_validate(&recipient)?;
+            read_mint_hook_failures_by_recipient(deps.storage, &recipient, start_after, limit)?
+        }
+        None => read_mint_hook_failures(deps.storage, start_after, limit)?,
+    };
+    let failures = mint_hook_failures
+        .into_iter()
+        .map(|(mint_id, failure)| MintHookFailureResponse {
+            mint_id,
+            minter: failure.minter,
+            recipient: failure.recipient,
+            contract: failure.contract,
+            amount: failure.amount,
+            error: failure.error,
+            height: failure.height,
+            timestamp: failure.timestamp,
+        })
+        .collect();
+    Ok(MintHookFailuresResponse { failures })
+}
+
+pub fn query_periodic_allowance(
+    deps: Deps,
+    env: Env,
+    owner: String,
+    spender: String,
+) -> StdResult<PeriodicAllowanceResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let spender = deps.api.addr_validate(&spender)?;
+    let periodic_allowance = match read_periodic_allowance(deps.storage, &owner, &spender)? {
+        Some(periodic_allowance) => periodic_allowance,
+        None => {
+            return Ok(PeriodicAllowanceResponse {
+                amount_per_period: Uint128::zero(),
+                period_seconds: 0,
+                period_start: 0,
+                spent: Uint128::zero(),
+                available: Uint128::zero(),
+                expires: Expiration::Never {},
+            })
+        }
+    };
+
+    let periodic_allowance =
+        refill_periodic_allowance(periodic_allowance, env.block.time.seconds());
+    let available = if periodic_allowance.expires.is_expired(&env.block) {
+        Uint128::zero()
+    } else {
+        periodic_allowance
+            .amount_per_period
+            .saturating_sub(periodic_allowance.spent)
+    };
+    Ok(PeriodicAllowanceResponse {
+        amount_per_period: periodic_allowance.amount_per_period,
+        period_seconds: periodic_allowance.period_seconds,
+        period_start: periodic_allowance.period_start,
+        spent: periodic_allowance.spent,
+        available,
+        expires: periodic_allowance.expires,
+    })
+}
+
+pub fn query_vesting_balance(
+    deps: Deps,
+    env: Env,
+    address: String,
+) -> StdResult<VestingBalanceResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let balance = BALANCES
+        .may_load(deps.storage, &address)?
+        .unwrap_or_default();
+    let (locked, vested, schedule) = match read_vesting_account(deps.storage, &address)? {
+        Some(vesting_account) => {
+            let locked = vesting_locked(&vesting_account, env.block.time.seconds());
+            (
+                locked,
+                vesting_account.amount - locked,
+                Some(vesting_account.schedule),
+            )
+        }
+        None => (Uint128::zero(), Uint128::zero(), None),
+    };
+    Ok(VestingBalanceResponse {
+        balance,
+        locked,
+        vested,
+        spendable: balance.saturating_sub(locked),
+        schedule,
+    })
+}
+
+pub fn query_emission_schedule(
+    deps: Deps,
+    env: Env,
+) -> StdResult<Option<EmissionScheduleResponse>> {
+    let emission_config = match read_emission_config(deps.storage)? {
+        Some(emission_config) => emission_config,
+        None => return Ok(None),
+    };
+    let pending = emission_accrued(
+        &emission_config.curve,
+        emission_config.last_emitted,
+        env.block.time.seconds(),
+    )?;
+    Ok(Some(EmissionScheduleResponse {
+        curve: emission_config.curve,
+        receivers: emission_config
+            .receivers
+            .into_iter()
+            .map(|(address, weight)| EmissionReceiver {
+                address: address.to_string(),
+                weight,
+            })
+            .collect(),
+        last_emitted: emission_config.last_emitted,
+        pending,
+    }))
+}
+
+pub fn query_projected_supply(deps: Deps, env: Env, at: u64) -> StdResult<ProjectedSupplyResponse> {
+    if at < env.block.time.seconds() {
+        return Err(StdError::generic_err("Timestamp in the past"));
+    }
+    let supply = TOKEN_INFO.load(deps.storage)?.total_supply;
+    let pending = match read_emission_config(deps.storage)? {
+        Some(emission_config) => {
+            emission_accrued(&emission_config.curve, emission_config.last_emitted, at)?
+        }
+        None => Uint128::zero(),
+    };
+    let max_supply = Uint128::from(read_seilor_config(deps.storage)?.max_supply);
+    Ok(ProjectedSupplyResponse {
+        at,
+        supply: supply.saturating_add(pending).min(max_supply),
+    })
+}
+
+pub fn query_launch_limits(deps: Deps) -> StdResult<LaunchLimitsResponse> {
+    let launch_limits = read_launch_limits(deps.storage)?;
+    Ok(LaunchLimitsResponse {
+        max_transfer_amount: launch_limits.max_transfer_amount,
+        max_wallet_balance: launch_limits.max_wallet_balance,
+        transfer_cooldown_seconds: launch_limits.transfer_cooldown_seconds,
+        lifted: launch_limits.lifted,
+    })
+}
+
+pub fn query_limit_exempt_accounts(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<LimitExemptAccountsResponse> {
+    let start_after = match start_after {
+        Some(start_after) => Some(deps.api.addr_validate(&start_after)?),
+        None => None,
+    };
+    let accounts = read_limit_exempt_accounts(deps.storage, start_after, limit)?;
+    Ok(LimitExemptAccountsResponse { accounts })
+}
+
+pub fn query_mint_approval(deps: Deps) -> StdResult<MintApprovalResponse> {
+    let mint_approval_config = read_mint_approval_config(deps.storage)?;
+    Ok(MintApprovalResponse {
+        threshold: mint_approval_config.threshold,
+        window_seconds: mint_approval_config.window_seconds,
+    })
+}
+
+fn mint_proposal_response(env: &Env, id: u64, mint_proposal: MintProposal) -> MintProposalResponse {
+    MintProposalResponse {
+        id,
+        minter: mint_proposal.minter,
+        recipient: mint_proposal.recipient,
+        amount: mint_proposal.amount,
+        contract: mint_proposal.contract,
+        msg: mint_proposal.msg,
+        created_at: mint_proposal.created_at,
+        expires_at: mint_proposal.expires_at,
+        expired: env.block.time.seconds() >= mint_proposal.expires_at,
+    }
+}
+
+pub fn query_mint_proposal(deps: Deps, env: Env, id: u64) -> StdResult<MintProposalResponse> {
+    match read_mint_proposal(deps.storage, id)? {
+        Some(mint_proposal) => Ok(mint_proposal_response(&env, id, mint_proposal)),
+        None => Err(StdError::generic_err("Mint proposal not found")),
+    }
+}
+
+pub fn query_mint_proposals(
+    deps: Deps,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<MintProposalsResponse> {
+    let proposals = read_mint_proposals(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|(id, mint_proposal)| mint_proposal_response(&env, id, mint_proposal))
+        .collect();
+    Ok(MintProposalsResponse { proposals })
+}
+
+pub fn query_payments_received(
+    deps: Deps,
+    recipient: String,
+    reference: Option<String>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<PaymentsResponse> {
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let payments = read_payments_received(
+        deps.storage,
+        &recipient,
+        reference.as_deref(),
+        start_after,
+        limit,
+    )?
+    .into_iter()
+    .map(|(id, payment_record)| PaymentResponse {
+        id,
+        sender: payment_record.sender,
+        recipient: payment_record.recipient,
+        amount: payment_record.amount,
+        net_amount: payment_record.net_amount,
+        reference: payment_record.reference,
+        height: payment_record.height,
+        timestamp: payment_record.timestamp,
+    })
+    .collect();
+    Ok(PaymentsResponse { payments })
+}
@@ -1,19 +1,325 @@
-use cosmwasm_std::{Addr, StdError, StdResult, Storage};
-use cw_storage_plus::Item;
+use crate::msg::{EmissionCurve, VestingSchedule};
+use cosmwasm_std::{Addr, Binary, Order, StdError, StdResult, Storage, Uint128};
+use cw_storage_plus::{Bound, Item, Map, SnapshotItem, SnapshotMap, Strategy};
+use cw_utils::Expiration;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct SeilorConfig {
     pub max_supply: u128,
     pub fund: Addr,
     pub gov: Addr,
-    pub distribute: Addr,
+    // send RefreshReward to fund on every balance change
+    #[serde(default)]
+    pub refresh_reward_enabled: bool,
+    // whether minters may still mint to frozen accounts
+    #[serde(default)]
+    pub mint_to_frozen_enabled: bool,
+}
+
+// SeilorConfig as stored before the minter registry, only read by migrate
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacySeilorConfig {
+    pub max_supply: u128,
+    pub fund: Addr,
+    pub gov: Addr,
+    #[serde(default)]
+    pub distribute: Option<Addr>,
+    #[serde(default)]
+    pub refresh_reward_enabled: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MinterInfo {
+    // lifetime mint quota
+    pub quota: Uint128,
+    pub minted: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TransferTaxConfig {
+    // tax on every transfer and send, in basis points
+    pub tax_bps: u64,
+    // part of the tax that is burned, in basis points, the rest goes to the receiver
+    pub burn_bps: u64,
+    // empty means SeilorConfig.fund
+    pub receiver: Addr,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct LaunchLimits {
+    pub max_transfer_amount: Option<Uint128>,
+    pub max_wallet_balance: Option<Uint128>,
+    // seconds between two outgoing transfers of an address, 0 disables it
+    pub transfer_cooldown_seconds: u64,
+    // set once gov lifts the limits, they can't be set again
+    pub lifted: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BurnRecord {
+    pub account: Addr,
+    pub amount: Uint128,
+    pub reason: Option<String>,
+    pub height: u64,
+    pub timestamp: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PaymentRecord {
+    pub sender: Addr,
+    pub recipient: Addr,
+    pub amount: Uint128,
+    // amount received after the transfer tax
+    pub net_amount: Uint128,
+    pub reference: String,
+    pub height: u64,
+    pub timestamp: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Escrow {
+    pub sender: Addr,
+    pub recipient: Addr,
+    pub amount: Uint128,
+    // block time in seconds after which the recipient can claim
+    pub release_at: u64,
+    pub cancelable: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Stream {
+    pub sender: Addr,
+    pub recipient: Addr,
+    // total amount streamed between start and end
+    pub amount: Uint128,
+    pub withdrawn: Uint128,
+    // block times in seconds
+    pub start: u64,
+    pub end: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MintRecord {
+    pub minter: Addr,
+    pub recipient: Addr,
+    pub amount: Uint128,
+    pub height: u64,
+    pub timestamp: u64,
+    // Cw20MintReceiveMsg hook target, if any
+    pub contract: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct MintApprovalConfig {
+    // mints taking a minter above this within a window wait for gov, none disables it
+    pub threshold: Option<Uint128>,
+    // window length, also the seconds gov has to approve a mint proposal
+    pub window_seconds: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MintProposal {
+    pub minter: Addr,
+    pub recipient: Addr,
+    pub amount: Uint128,
+    // Cw20MintReceiveMsg hook, sent once approved
+    pub contract: Option<Addr>,
+    pub msg: Option<Binary>,
+    pub created_at: u64,
+    pub expires_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct MintWindow {
+    pub start: u64,
+    // minted without approval since start
+    pub minted: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MintHookFailure {
+    pub minter: Addr,
+    pub recipient: Addr,
+    pub contract: Addr,
+    pub amount: Uint128,
+    pub error: String,
+    pub height: u64,
+    pub timestamp: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PeriodicAllowance {
+    pub amount_per_period: Uint128,
+    pub period_seconds: u64,
+    // block time in seconds the current period started at
+    pub period_start: u64,
+    // spent in the current period
+    pub spent: Uint128,
+    pub expires: Expiration,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VestingAccount {
+    pub amount: Uint128,
+    pub schedule: VestingSchedule,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EmissionConfig {
+    pub curve: EmissionCurve,
+    // receivers and their weights
+    pub receivers: Vec<(Addr, u64)>,
+    // block time emission has been minted up to
+    pub last_emitted: u64,
+}
+
+// holders from before a migrate still to be seeded by SeedHolders, walked in address order
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HolderSeed {
+    // checkpoint balances and votes too, not only the holders index
+    pub checkpoint: bool,
+    pub start_after: Option<Addr>,
 }
 
 const SEILOR_CONFIG: Item<SeilorConfig> = Item::new("seilor_config");
 
-pub fn store_seilor_config(storage: &mut dyn Storage, seilor_config: &SeilorConfig) -> StdResult<()> {
+const LEGACY_SEILOR_CONFIG: Item<LegacySeilorConfig> = Item::new("seilor_config");
+
+const MINTERS: Map<Addr, MinterInfo> = Map::new("minters");
+
+const FROZEN_ACCOUNTS: Map<&Addr, bool> = Map::new("frozen_accounts");
+
+const TRANSFER_TAX_CONFIG: Item<TransferTaxConfig> = Item::new("transfer_tax_config");
+
+const TAX_EXEMPT_ACCOUNTS: Map<&Addr, bool> = Map::new("tax_exempt_accounts");
+
+const LAUNCH_LIMITS: Item<LaunchLimits> = Item::new("launch_limits");
+
+const LIMIT_EXEMPT_ACCOUNTS: Map<&Addr, bool> = Map::new("limit_exempt_accounts");
+
+// block time of each address's last outgoing transfer, for the launch cooldown
+const LAST_TRANSFER_TIMES: Map<&Addr, u64> = Map::new("last_transfer_times");
+
+// accounts whose balance is not part of the circulating supply
+const SUPPLY_EXCLUDED_ACCOUNTS: Map<&Addr, bool> = Map::new("supply_excluded_accounts");
+
+// mint id -> record, ids start at 1
+const MINT_LOG: Map<u64, MintRecord> = Map::new("mint_log");
+
+const MINT_LOG_COUNT: Item<u64> = Item::new("mint_log_count");
+
+const MINT_LOG_BY_MINTER: Map<(&Addr, u64), bool> = Map::new("mint_log_by_minter");
+
+const MINT_APPROVAL_CONFIG: Item<MintApprovalConfig> = Item::new("mint_approval_config");
+
+// pending mint proposals, removed once approved or rejected, the amount is reserved from the minter's quota
+const MINT_PROPOSALS: Map<u64, MintProposal> = Map::new("mint_proposals");
+
+const MINT_WINDOWS: Map<&Addr, MintWindow> = Map::new("mint_windows");
+
+const MINT_PROPOSAL_COUNT: Item<u64> = Item::new("mint_proposal_count");
+
+// full token-factory denom SEILOR is wrapped into
+const WRAP_DENOM: Item<String> = Item::new("wrap_denom");
+
+// cw20 SEILOR held by the contract backing the wrapped denom
+const WRAPPED_SUPPLY: Item<Uint128> = Item::new("wrapped_supply");
+
+// mint id -> failed mint hook
+const MINT_HOOK_FAILURES: Map<u64, MintHookFailure> = Map::new("mint_hook_failures");
+
+const MINT_HOOK_FAILURES_BY_RECIPIENT: Map<(&Addr, u64), bool> =
+    Map::new("mint_hook_failures_by_recipient");
+
+// minted tokens held for recipients whose mint hook failed
+const MINT_ESCROWS: Map<&Addr, Uint128> = Map::new("mint_escrows");
+
+// (owner, spender) -> periodic allowance
+const PERIODIC_ALLOWANCES: Map<(&Addr, &Addr), PeriodicAllowance> = Map::new("periodic_allowances");
+
+const VESTING_ACCOUNTS: Map<&Addr, VestingAccount> = Map::new("vesting_accounts");
+
+const EMISSION_CONFIG: Item<EmissionConfig> = Item::new("emission_config");
+
+const TOTAL_BURNED: Item<Uint128> = Item::new("total_burned");
+
+const BURNED: Map<&Addr, Uint128> = Map::new("burned");
+
+// burn id -> record, ids start at 1
+const BURN_LOG: Map<u64, BurnRecord> = Map::new("burn_log");
+
+const BURN_LOG_COUNT: Item<u64> = Item::new("burn_log_count");
+
+// transfers and sends with a reference, keyed by payment id
+const PAYMENTS: Map<u64, PaymentRecord> = Map::new("payments");
+
+const PAYMENT_COUNT: Item<u64> = Item::new("payment_count");
+
+const PAYMENTS_BY_RECIPIENT: Map<(&Addr, u64), bool> = Map::new("payments_by_recipient");
+
+const PAYMENTS_BY_REFERENCE: Map<(&Addr, &str, u64), bool> = Map::new("payments_by_reference");
+
+const ESCROWS: Map<u64, Escrow> = Map::new("escrows");
+
+const ESCROW_COUNT: Item<u64> = Item::new("escrow_count");
+
+const ESCROWS_BY_SENDER: Map<(&Addr, u64), bool> = Map::new("escrows_by_sender");
+
+const ESCROWS_BY_RECIPIENT: Map<(&Addr, u64), bool> = Map::new("escrows_by_recipient");
+
+// open streams by id, ids start at 1
+const STREAMS: Map<u64, Stream> = Map::new("streams");
+
+const STREAM_COUNT: Item<u64> = Item::new("stream_count");
+
+const STREAMS_BY_SENDER: Map<(&Addr, u64), bool> = Map::new("streams_by_sender");
+
+const STREAMS_BY_RECIPIENT: Map<(&Addr, u64), bool> = Map::new("streams_by_recipient");
+
+// owner -> next permit nonce
+const PERMIT_NONCES: Map<&Addr, u64> = Map::new("permit_nonces");
+
+// balance and total supply checkpoints, mirrored from cw20-base after every balance change
+const BALANCE_SNAPSHOTS: SnapshotMap<&Addr, Uint128> = SnapshotMap::new(
+    "balance_snapshots",
+    "balance_snapshots__checkpoints",
+    "balance_snapshots__changelog",
+    Strategy::EveryBlock,
+);
+
+// (balance, holder) index of every non-zero balance, kept in sync by checkpoint_balances
+const HOLDERS_BY_BALANCE: Map<(u128, &Addr), bool> = Map::new("holders_by_balance");
+
+const HOLDER_SEED: Item<HolderSeed> = Item::new("holder_seed");
+
+// delegator -> delegatee
+const DELEGATES: Map<&Addr, Addr> = Map::new("delegates");
+
+// delegatee -> voting power checkpoints
+const VOTES: SnapshotMap<&Addr, Uint128> = SnapshotMap::new(
+    "votes",
+    "votes__checkpoints",
+    "votes__changelog",
+    Strategy::EveryBlock,
+);
+
+const TOTAL_SUPPLY_SNAPSHOTS: SnapshotItem<Uint128> = SnapshotItem::new(
+    "total_supply_snapshots",
+    "total_supply_snapshots__checkpoints",
+    "total_supply_snapshots__changelog",
+    Strategy::EveryBlock,
+);
+
+pub fn store_seilor_config(
+    storage: &mut dyn Storage,
+    seilor_config: &SeilorConfig,
+) -> StdResult<()> {
     SEILOR_CONFIG.save(storage, seilor_config)
 }
 
@@ -22,3 +328,730 @@ pub fn read_seilor_config(storage: &dyn Storage) -> StdResult<SeilorConfig> {
         .load(storage)
         .map_err(|_| StdError::generic_err("SeilorConfig not found"))
 }
+
+pub fn read_legacy_seilor_config(storage: &dyn Storage) -> StdResult<LegacySeilorConfig> {
+    LEGACY_SEILOR_CONFIG.load(storage)
+}
+
+pub fn store_minter_info(
+    storage: &mut dyn Storage,
+    minter: &Addr,
+    minter_info: &MinterInfo,
+) -> StdResult<()> {
+    MINTERS.save(storage, minter.clone(), minter_info)
+}
+
+pub fn read_minter_info(storage: &dyn Storage, minter: &Addr) -> StdResult<Option<MinterInfo>> {
+    MINTERS.may_load(storage, minter.clone())
+}
+
+pub fn remove_minter_info(storage: &mut dyn Storage, minter: &Addr) {
+    MINTERS.remove(storage, minter.clone())
+}
+
+pub fn read_minter_infos(
+    storage: &dyn Storage,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(Addr, MinterInfo)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    MINTERS
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+pub fn read_balance_snapshot(storage: &dyn Storage, user: &Addr) -> StdResult<Option<Uint128>> {
+    BALANCE_SNAPSHOTS.may_load(storage, user)
+}
+
+pub fn store_balance_snapshot(
+    storage: &mut dyn Storage,
+    user: &Addr,
+    balance: &Uint128,
+    height: u64,
+) -> StdResult<()> {
+    BALANCE_SNAPSHOTS.save(storage, user, balance, height)
+}
+
+// balance at the start of block `height`
+pub fn read_balance_snapshot_at(
+    storage: &dyn Storage,
+    user: &Addr,
+    height: u64,
+) -> StdResult<Option<Uint128>> {
+    BALANCE_SNAPSHOTS.may_load_at_height(storage, user, height)
+}
+
+pub fn store_total_supply_snapshot(
+    storage: &mut dyn Storage,
+    total_supply: &Uint128,
+    height: u64,
+) -> StdResult<()> {
+    TOTAL_SUPPLY_SNAPSHOTS.save(storage, total_supply, height)
+}
+
+pub fn read_total_supply_snapshot(storage: &dyn Storage) -> StdResult<Option<Uint128>> {
+    TOTAL_SUPPLY_SNAPSHOTS.may_load(storage)
+}
+
+// total supply at the start of block `height`
+pub fn read_total_supply_snapshot_at(
+    storage: &dyn Storage,
+    height: u64,
+) -> StdResult<Option<Uint128>> {
+    TOTAL_SUPPLY_SNAPSHOTS.may_load_at_height(storage, height)
+}
+
+pub fn store_delegate(
+    storage: &mut dyn Storage,
+    delegator: &Addr,
+    delegatee: &Addr,
+) -> StdResult<()> {
+    DELEGATES.save(storage, delegator, delegatee)
+}
+
+pub fn read_delegate(storage: &dyn Storage, delegator: &Addr) -> StdResult<Option<Addr>> {
+    DELEGATES.may_load(storage, delegator)
+}
+
+pub fn store_votes(
+    storage: &mut dyn Storage,
+    delegatee: &Addr,
+    votes: &Uint128,
+    height: u64,
+) -> StdResult<()> {
+    VOTES.save(storage, delegatee, votes, height)
+}
+
+pub fn read_votes(storage: &dyn Storage, delegatee: &Addr) -> StdResult<Uint128> {
+    Ok(VOTES.may_load(storage, delegatee)?.unwrap_or_default())
+}
+
+// voting power at the start of block `height`
+pub fn read_votes_at(storage: &dyn Storage, delegatee: &Addr, height: u64) -> StdResult<Uint128> {
+    Ok(VOTES
+        .may_load_at_height(storage, delegatee, height)?
+        .unwrap_or_default())
+}
+
+pub fn store_frozen_account(storage: &mut dyn Storage, account: &Addr) -> StdResult<()> {
+    FROZEN_ACCOUNTS.save(storage, account, &true)
+}
+
+pub fn remove_frozen_account(storage: &mut dyn Storage, account: &Addr) {
+    FROZEN_ACCOUNTS.remove(storage, account)
+}
+
+pub fn is_frozen_account(storage: &dyn Storage, account: &Addr) -> bool {
+    FROZEN_ACCOUNTS.has(storage, account)
+}
+
+pub fn read_frozen_accounts(
+    storage: &dyn Storage,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Addr>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_ref().map(Bound::exclusive);
+    FROZEN_ACCOUNTS
+        .keys(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+pub fn store_transfer_tax_config(
+    storage: &mut dyn Storage,
+    transfer_tax_config: &TransferTaxConfig,
+) -> StdResult<()> {
+    TRANSFER_TAX_CONFIG.save(storage, transfer_tax_config)
+}
+
+// transfers are untaxed until gov sets a tax
+pub fn read_transfer_tax_config(storage: &dyn Storage) -> StdResult<TransferTaxConfig> {
+    Ok(TRANSFER_TAX_CONFIG
+        .may_load(storage)?
+        .unwrap_or_else(|| TransferTaxConfig {
+            tax_bps: 0,
+            burn_bps: 0,
+            receiver: Addr::unchecked(""),
+        }))
+}
+
+pub fn store_tax_exempt_account(storage: &mut dyn Storage, account: &Addr) -> StdResult<()> {
+    TAX_EXEMPT_ACCOUNTS.save(storage, account, &true)
+}
+
+pub fn remove_tax_exempt_account(storage: &mut dyn Storage, account: &Addr) {
+    TAX_EXEMPT_ACCOUNTS.remove(storage, account)
+}
+
+pub fn is_tax_exempt_account(storage: &dyn Storage, account: &Addr) -> bool {
+    TAX_EXEMPT_ACCOUNTS.has(storage, account)
+}
+
+pub fn read_tax_exempt_accounts(
+    storage: &dyn Storage,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Addr>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_ref().map(Bound::exclusive);
+    TAX_EXEMPT_ACCOUNTS
+        .keys(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+pub fn store_launch_limits(
+    storage: &mut dyn Storage,
+    launch_limits: &LaunchLimits,
+) -> StdResult<()> {
+    LAUNCH_LIMITS.save(storage, launch_limits)
+}
+
+pub fn read_launch_limits(storage: &dyn Storage) -> StdResult<LaunchLimits> {
+    Ok(LAUNCH_LIMITS.may_load(storage)?.unwrap_or_default())
+}
+
+pub fn store_limit_exempt_account(storage: &mut dyn Storage, account: &Addr) -> StdResult<()> {
+    LIMIT_EXEMPT_ACCOUNTS.save(storage, account, &true)
+}
+
+pub fn remove_limit_exempt_account(storage: &mut dyn Storage, account: &Addr) {
+    LIMIT_EXEMPT_ACCOUNTS.remove(storage, account)
+}
+
+pub fn is_limit_exempt_account(storage: &dyn Storage, account: &Addr) -> bool {
+    LIMIT_EXEMPT_ACCOUNTS.has(storage, account)
+}
+
+pub fn read_limit_exempt_accounts(
+    storage: &dyn Storage,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Addr>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_ref().map(Bound::exclusive);
+    LIMIT_EXEMPT_ACCOUNTS
+        .keys(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+pub fn store_last_transfer_time(
+    storage: &mut dyn Storage,
+    account: &Addr,
+    time: u64,
+) -> StdResult<()> {
+    LAST_TRANSFER_TIMES.save(storage, account, &time)
+}
+
+pub fn read_last_transfer_time(storage: &dyn Storage, account: &Addr) -> StdResult<Option<u64>> {
+    LAST_TRANSFER_TIMES.may_load(storage, account)
+}
+
+pub fn store_permit_nonce(storage: &mut dyn Storage, owner: &Addr, nonce: u64) -> StdResult<()> {
+    PERMIT_NONCES.save(storage, owner, &nonce)
+}
+
+pub fn read_permit_nonce(storage: &dyn Storage, owner: &Addr) -> StdResult<u64> {
+    Ok(PERMIT_NONCES.may_load(storage, owner)?.unwrap_or_default())
+}
+
+pub fn store_total_burned(storage: &mut dyn Storage, total_burned: &Uint128) -> StdResult<()> {
+    TOTAL_BURNED.save(storage, total_burned)
+}
+
+pub fn read_total_burned(storage: &dyn Storage) -> StdResult<Uint128> {
+    Ok(TOTAL_BURNED.may_load(storage)?.unwrap_or_default())
+}
+
+pub fn store_burned(storage: &mut dyn Storage, account: &Addr, burned: &Uint128) -> StdResult<()> {
+    BURNED.save(storage, account, burned)
+}
+
+pub fn read_burned(storage: &dyn Storage, account: &Addr) -> StdResult<Uint128> {
+    Ok(BURNED.may_load(storage, account)?.unwrap_or_default())
+}
+
+/// Appends a record to the burn log and returns its id.
+pub fn append_burn_record(storage: &mut dyn Storage, burn_record: &BurnRecord) -> StdResult<u64> {
+    let id = BURN_LOG_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    BURN_LOG.save(storage, id, burn_record)?;
+    BURN_LOG_COUNT.save(storage, &id)?;
+    Ok(id)
+}
+
+pub fn read_burn_records(
+    storage: &dyn Storage,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, BurnRecord)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    BURN_LOG
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+pub fn store_supply_excluded_account(storage: &mut dyn Storage, account: &Addr) -> StdResult<()> {
+    SUPPLY_EXCLUDED_ACCOUNTS.save(storage, account, &true)
+}
+
+pub fn remove_supply_excluded_account(storage: &mut dyn Storage, account: &Addr) {
+    SUPPLY_EXCLUDED_ACCOUNTS.remove(storage, account)
+}
+
+pub fn read_supply_excluded_accounts(
+    storage: &dyn Storage,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Addr>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_ref().map(Bound::exclusive);
+    SUPPLY_EXCLUDED_ACCOUNTS
+        .keys(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+// the list is capped by set_supply_excluded, so the circulating supply can walk all of it
+pub fn read_all_supply_excluded_accounts(storage: &dyn Storage) -> StdResult<Vec<Addr>> {
+    SUPPLY_EXCLUDED_ACCOUNTS
+        .keys(storage, None, None, Order::Ascending)
+        .collect()
+}
+
+pub fn store_holder_balance(
+    storage: &mut dyn Storage,
+    holder: &Addr,
+    balance: Uint128,
+) -> StdResult<()> {
+    HOLDERS_BY_BALANCE.save(storage, (balance.u128(), holder), &true)
+}
+
+pub fn remove_holder_balance(storage: &mut dyn Storage, holder: &Addr, balance: Uint128) {
+    HOLDERS_BY_BALANCE.remove(storage, (balance.u128(), holder))
+}
+
+pub fn is_holders_index_empty(storage: &dyn Storage) -> bool {
+    HOLDERS_BY_BALANCE
+        .keys(storage, None, None, Order::Ascending)
+        .next()
+        .is_none()
+}
+
+pub fn store_holder_seed(storage: &mut dyn Storage, holder_seed: &HolderSeed) -> StdResult<()> {
+    HOLDER_SEED.save(storage, holder_seed)
+}
+
+pub fn read_holder_seed(storage: &dyn Storage) -> StdResult<Option<HolderSeed>> {
+    HOLDER_SEED.may_load(storage)
+}
+
+pub fn remove_holder_seed(storage: &mut dyn Storage) {
+    HOLDER_SEED.remove(storage)
+}
+
+/// Holders with the largest balance first, ties in descending address order.
+pub fn read_top_holders(
+    storage: &dyn Storage,
+    start_after: Option<(Uint128, Addr)>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(Addr, Uint128)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let end = start_after
+        .as_ref()
+        .map(|(balance, holder)| Bound::exclusive((balance.u128(), holder)));
+    HOLDERS_BY_BALANCE
+        .keys(storage, None, end, Order::Descending)
+        .take(limit)
+        .map(|key| key.map(|(balance, holder)| (holder, Uint128::from(balance))))
+        .collect()
+}
+
+pub fn next_escrow_id(storage: &mut dyn Storage) -> StdResult<u64> {
+    let id = ESCROW_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    ESCROW_COUNT.save(storage, &id)?;
+    Ok(id)
+}
+
+pub fn store_escrow(storage: &mut dyn Storage, id: u64, escrow: &Escrow) -> StdResult<()> {
+    ESCROWS.save(storage, id, escrow)?;
+    ESCROWS_BY_SENDER.save(storage, (&escrow.sender, id), &true)?;
+    ESCROWS_BY_RECIPIENT.save(storage, (&escrow.recipient, id), &true)
+}
+
+pub fn read_escrow(storage: &dyn Storage, id: u64) -> StdResult<Option<Escrow>> {
+    ESCROWS.may_load(storage, id)
+}
+
+pub fn remove_escrow(storage: &mut dyn Storage, id: u64, escrow: &Escrow) {
+    ESCROWS.remove(storage, id);
+    ESCROWS_BY_SENDER.remove(storage, (&escrow.sender, id));
+    ESCROWS_BY_RECIPIENT.remove(storage, (&escrow.recipient, id));
+}
+
+pub fn read_escrows_by_sender(
+    storage: &dyn Storage,
+    sender: &Addr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, Escrow)>> {
+    read_escrows_by(storage, ESCROWS_BY_SENDER, sender, start_after, limit)
+}
+
+pub fn read_escrows_by_recipient(
+    storage: &dyn Storage,
+    recipient: &Addr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, Escrow)>> {
+    read_escrows_by(storage, ESCROWS_BY_RECIPIENT, recipient, start_after, limit)
+}
+
+fn read_escrows_by(
+    storage: &dyn Storage,
+    index: Map<(&Addr, u64), bool>,
+    account: &Addr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, Escrow)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    index
+        .prefix(account)
+        .keys(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|id| {
+            let id = id?;
+            Ok((id, ESCROWS.load(storage, id)?))
+        })
+        .collect()
+}
+
+pub fn next_stream_id(storage: &mut dyn Storage) -> StdResult<u64> {
+    let id = STREAM_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    STREAM_COUNT.save(storage, &id)?;
+    Ok(id)
+}
+
+pub fn store_stream(storage: &mut dyn Storage, id: u64, stream: &Stream) -> StdResult<()> {
+    STREAMS.save(storage, id, stream)?;
+    STREAMS_BY_SENDER.save(storage, (&stream.sender, id), &true)?;
+    STREAMS_BY_RECIPIENT.save(storage, (&stream.recipient, id), &true)
+}
+
+pub fn read_stream(storage: &dyn Storage, id: u64) -> StdResult<Option<Stream>> {
+    STREAMS.may_load(storage, id)
+}
+
+pub fn remove_stream(storage: &mut dyn Storage, id: u64, stream: &Stream) {
+    STREAMS.remove(storage, id);
+    STREAMS_BY_SENDER.remove(storage, (&stream.sender, id));
+    STREAMS_BY_RECIPIENT.remove(storage, (&stream.recipient, id));
+}
+
+pub fn read_streams_by_sender(
+    storage: &dyn Storage,
+    sender: &Addr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, Stream)>> {
+    read_streams_by(storage, STREAMS_BY_SENDER, sender, start_after, limit)
+}
+
+pub fn read_streams_by_recipient(
+    storage: &dyn Storage,
+    recipient: &Addr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, Stream)>> {
+    read_streams_by(storage, STREAMS_BY_RECIPIENT, recipient, start_after, limit)
+}
+
+fn read_streams_by(
+    storage: &dyn Storage,
+    index: Map<(&Addr, u64), bool>,
+    account: &Addr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, Stream)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    index
+        .prefix(account)
+        .keys(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|id| {
+            let id = id?;
+            Ok((id, STREAMS.load(storage, id)?))
+        })
+        .collect()
+}
+
+/// Appends a record to the mint log and returns its id.
+pub fn append_mint_record(storage: &mut dyn Storage, mint_record: &MintRecord) -> StdResult<u64> {
+    let id = MINT_LOG_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    MINT_LOG.save(storage, id, mint_record)?;
+    MINT_LOG_BY_MINTER.save(storage, (&mint_record.minter, id), &true)?;
+    MINT_LOG_COUNT.save(storage, &id)?;
+    Ok(id)
+}
+
+pub fn read_mint_record(storage: &dyn Storage, id: u64) -> StdResult<MintRecord> {
+    MINT_LOG.load(storage, id)
+}
+
+pub fn read_mint_records(
+    storage: &dyn Storage,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, MintRecord)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    MINT_LOG
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+pub fn read_mint_records_by_minter(
+    storage: &dyn Storage,
+    minter: &Addr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, MintRecord)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    MINT_LOG_BY_MINTER
+        .prefix(minter)
+        .keys(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|id| {
+            let id = id?;
+            Ok((id, MINT_LOG.load(storage, id)?))
+        })
+        .collect()
+}
+
+pub fn store_mint_approval_config(
+    storage: &mut dyn Storage,
+    mint_approval_config: &MintApprovalConfig,
+) -> StdResult<()> {
+    MINT_APPROVAL_CONFIG.save(storage, mint_approval_config)
+}
+
+pub fn read_mint_approval_config(storage: &dyn Storage) -> StdResult<MintApprovalConfig> {
+    Ok(MINT_APPROVAL_CONFIG.may_load(storage)?.unwrap_or_default())
+}
+
+pub fn store_mint_window(
+    storage: &mut dyn Storage,
+    minter: &Addr,
+    mint_window: &MintWindow,
+) -> StdResult<()> {
+    MINT_WINDOWS.save(storage, minter, mint_window)
+}
+
+pub fn read_mint_window(storage: &dyn Storage, minter: &Addr) -> StdResult<MintWindow> {
+    Ok(MINT_WINDOWS.may_load(storage, minter)?.unwrap_or_default())
+}
+
+pub fn append_mint_proposal(
+    storage: &mut dyn Storage,
+    mint_proposal: &MintProposal,
+) -> StdResult<u64> {
+    let id = MINT_PROPOSAL_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    MINT_PROPOSALS.save(storage, id, mint_proposal)?;
+    MINT_PROPOSAL_COUNT.save(storage, &id)?;
+    Ok(id)
+}
+
+pub fn read_mint_proposal(storage: &dyn Storage, id: u64) -> StdResult<Option<MintProposal>> {
+    MINT_PROPOSALS.may_load(storage, id)
+}
+
+pub fn remove_mint_proposal(storage: &mut dyn Storage, id: u64) {
+    MINT_PROPOSALS.remove(storage, id)
+}
+
+pub fn read_mint_proposals(
+    storage: &dyn Storage,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, MintProposal)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    MINT_PROPOSALS
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+pub fn store_wrap_denom(storage: &mut dyn Storage, denom: &String) -> StdResult<()> {
+    WRAP_DENOM.save(storage, denom)
+}
+
+pub fn read_wrap_denom(storage: &dyn Storage) -> StdResult<Option<String>> {
+    WRAP_DENOM.may_load(storage)
+}
+
+pub fn store_wrapped_supply(storage: &mut dyn Storage, wrapped_supply: &Uint128) -> StdResult<()> {
+    WRAPPED_SUPPLY.save(storage, wrapped_supply)
+}
+
+pub fn read_wrapped_supply(storage: &dyn Storage) -> StdResult<Uint128> {
+    Ok(WRAPPED_SUPPLY.may_load(storage)?.unwrap_or_default())
+}
+
+pub fn store_mint_hook_failure(
+    storage: &mut dyn Storage,
+    mint_id: u64,
+    failure: &MintHookFailure,
+) -> StdResult<()> {
+    MINT_HOOK_FAILURES.save(storage, mint_id, failure)?;
+    MINT_HOOK_FAILURES_BY_RECIPIENT.save(storage, (&failure.recipient, mint_id), &true)
+}
+
+pub fn read_mint_hook_failures(
+    storage: &dyn Storage,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, MintHookFailure)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    MINT_HOOK_FAILURES
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+pub fn read_mint_hook_failures_by_recipient(
+    storage: &dyn Storage,
+    recipient: &Addr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, MintHookFailure)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    MINT_HOOK_FAILURES_BY_RECIPIENT
+        .prefix(recipient)
+        .keys(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|mint_id| {
+            let mint_id = mint_id?;
+            Ok((mint_id, MINT_HOOK_FAILURES.load(storage, mint_id)?))
+        })
+        .collect()
+}
+
+pub fn store_mint_escrow(
+    storage: &mut dyn Storage,
+    recipient: &Addr,
+    amount: &Uint128,
+) -> StdResult<()> {
+    MINT_ESCROWS.save(storage, recipient, amount)
+}
+
+pub fn read_mint_escrow(storage: &dyn Storage, recipient: &Addr) -> StdResult<Uint128> {
+    Ok(MINT_ESCROWS
+        .may_load(storage, recipient)?
+        .unwrap_or_default())
+}
+
+pub fn remove_mint_escrow(storage: &mut dyn Storage, recipient: &Addr) {
+    MINT_ESCROWS.remove(storage, recipient)
+}
+
+pub fn store_periodic_allowance(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    spender: &Addr,
+    periodic_allowance: &PeriodicAllowance,
+) -> StdResult<()> {
+    PERIODIC_ALLOWANCES.save(storage, (owner, spender), periodic_allowance)
+}
+
+pub fn read_periodic_allowance(
+    storage: &dyn Storage,
+    owner: &Addr,
+    spender: &Addr,
+) -> StdResult<Option<PeriodicAllowance>> {
+    PERIODIC_ALLOWANCES.may_load(storage, (owner, spender))
+}
+
+pub fn remove_periodic_allowance(storage: &mut dyn Storage, owner: &Addr, spender: &Addr) {
+    PERIODIC_ALLOWANCES.remove(storage, (owner, spender))
+}
+
+pub fn store_vesting_account(
+    storage: &mut dyn Storage,
+    account: &Addr,
+    vesting_account: &VestingAccount,
+) -> StdResult<()> {
+    VESTING_ACCOUNTS.save(storage, account, vesting_account)
+}
+
+pub fn read_vesting_account(
+    storage: &dyn Storage,
+    account: &Addr,
+) -> StdResult<Option<VestingAccount>> {
+    VESTING_ACCOUNTS.may_load(storage, account)
+}
+
+pub fn store_emission_config(
+    storage: &mut dyn Storage,
+    emission_config: &EmissionConfig,
+) -> StdResult<()> {
+    EMISSION_CONFIG.save(storage, emission_config)
+}
+
+pub fn read_emission_config(storage: &dyn Storage) -> StdResult<Option<EmissionConfig>> {
+    EMISSION_CONFIG.may_load(storage)
+}
+
+pub fn append_payment_record(
+    storage: &mut dyn Storage,
+    payment_record: &PaymentRecord,
+) -> StdResult<u64> {
+    let id = PAYMENT_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    PAYMENTS.save(storage, id, payment_record)?;
+    PAYMENTS_BY_RECIPIENT.save(storage, (&payment_record.recipient, id), &true)?;
+    PAYMENTS_BY_REFERENCE.save(
+        storage,
+        (&payment_record.recipient, &payment_record.reference, id),
+        &true,
+    )?;
+    PAYMENT_COUNT.save(storage, &id)?;
+    Ok(id)
+}
+
+pub fn read_payments_received(
+    storage: &dyn Storage,
+    recipient: &Addr,
+    reference: Option<&str>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, PaymentRecord)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let ids = match reference {
+        Some(reference) => PAYMENTS_BY_REFERENCE
+            .prefix((recipient, reference))
+            .keys(storage, start, None, Order::Ascending)
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?,
+        None => PAYMENTS_BY_RECIPIENT
+            .prefix(recipient)
+            .keys(storage, start, None, Order::Ascending)
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?,
+    };
+    ids.into_iter()
+        .map(|id| Ok((id, PAYMENTS.load(storage, id)?)))
+        .collect()
+}
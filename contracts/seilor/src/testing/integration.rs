@@ -0,0 +1,766 @@
+use crate::contract::{execute, instantiate, query, reply};
+use crate::error::ContractError;
+use crate::handler::{create_wrap_denom, unwrap, wrap};
+use crate::msg::{
+    DelegationResponse, ExecuteMsg, InstantiateMsg, MintEscrowResponse, MintHookFailuresResponse,
+    QueryMsg, TotalSupplyAtResponse, VestingSchedule, VotesResponse, WrappedSupplyResponse,
+};
+use crate::testing::mock_fn::{
+    mock_instantiate_msg, CREATOR, MAX_SUPPLY, MINTER, USER_A, USER_B, USER_C,
+};
+use crate::testing::mock_third_fn::{
+    mock_fund_contract, MockFundQueryMsg, MockTokenFactory, MockTokenFactoryMsg,
+};
+use crate::token_factory::into_custom_response;
+use cosmwasm_std::testing::{mock_env, MockApi, MockStorage};
+use cosmwasm_std::{coins, Addr, Binary, DepsMut, Empty, Env, MessageInfo, Response, Uint128};
+use cw20::BalanceResponse;
+use cw_multi_test::{App, AppBuilder, BankKeeper, ContractWrapper, Executor, WasmKeeper};
+
+fn mock_app() -> App {
+    AppBuilder::new()
+        .with_block(mock_env().block)
+        .build(|_, _, _| {})
+}
+
+fn seilor_contract_instance(creator: &Addr, app: &mut App) -> Addr {
+    let seilor_contract =
+        Box::new(ContractWrapper::new(execute, instantiate, query).with_reply(reply));
+    let seilor_code_id = app.store_code(seilor_contract);
+    app.instantiate_contract(
+        seilor_code_id,
+        creator.clone(),
+        &mock_instantiate_msg(),
+        &[], // no funds
+        String::from("SEILOR"),
+        None,
+    )
+    .unwrap()
+}
+
+fn fund_contract_instance(creator: &Addr, app: &mut App) -> Addr {
+    let fund_code_id = app.store_code(mock_fund_contract());
+    app.instantiate_contract(
+        fund_code_id,
+        creator.clone(),
+        &Empty {},
+        &[], // no funds
+        String::from("FUND"),
+        None,
+    )
+    .unwrap()
+}
+
+fn update_seilor_config(
+    creator: &Addr,
+    app: &mut App,
+    seilor_token: &Addr,
+    fund: &Addr,
+    refresh_reward_enabled: bool,
+) {
+    let update_config_msg = ExecuteMsg::UpdateConfig {
+        fund: Some(fund.clone()),
+        gov: None,
+        refresh_reward_enabled: Some(refresh_reward_enabled),
+        mint_to_frozen_enabled: None,
+    };
+    let res = app.execute_contract(
+        creator.clone(),
+        seilor_token.clone(),
+        &update_config_msg,
+        &[], // no funds
+    );
+    assert!(res.is_ok());
+}
+
+fn set_seilor_minter(creator: &Addr, app: &mut App, seilor_token: &Addr, minter: &str) {
+    let set_minter_msg = ExecuteMsg::SetMinter {
+        minter: minter.to_string(),
+        quota: Uint128::from(MAX_SUPPLY),
+    };
+    let res = app.execute_contract(
+        creator.clone(),
+        seilor_token.clone(),
+        &set_minter_msg,
+        &[], // no funds
+    );
+    assert!(res.is_ok());
+}
+
+fn execute_seilor(app: &mut App, sender: &str, seilor_token: &Addr, msg: &ExecuteMsg) {
+    let res = app.execute_contract(
+        Addr::unchecked(sender),
+        seilor_token.clone(),
+        msg,
+        &[], // no funds
+    );
+    assert!(res.is_ok(), "execute error:{:?}", res.err());
+}
+
+fn query_refreshed_balances(app: &App, fund: &Addr) -> Vec<(Addr, Uint128)> {
+    app.wrap()
+        .query_wasm_smart(fund.clone(), &MockFundQueryMsg::RefreshedBalances {})
+        .unwrap()
+}
+
+fn query_refreshed_users(app: &App, fund: &Addr) -> Vec<Addr> {
+    app.wrap()
+        .query_wasm_smart(fund.clone(), &MockFundQueryMsg::RefreshedUsers {})
+        .unwrap()
+}
+
+fn get_seilor_balance(app: &App, seilor_token: &Addr, user: &str) -> Uint128 {
+    let res: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            seilor_token.clone(),
+            &QueryMsg::Balance {
+                address: user.to_string(),
+            },
+        )
+        .unwrap();
+    res.balance
+}
+
+#[test]
+fn test_integration_refresh_reward_hook() {
+    let creator = Addr::unchecked(CREATOR);
+    let user_a = Addr::unchecked(USER_A);
+    let user_b = Addr::unchecked(USER_B);
+    let mut app = mock_app();
+
+    let seilor_token = seilor_contract_instance(&creator, &mut app);
+    let fund = fund_contract_instance(&creator, &mut app);
+
+    set_seilor_minter(&creator, &mut app, &seilor_token, MINTER);
+
+    // hook disabled, mint must not call the fund
+    update_seilor_config(&creator, &mut app, &seilor_token, &fund, false);
+    let mint_msg = ExecuteMsg::Mint {
+        recipient: USER_A.to_string(),
+        amount: Uint128::from(1000u128),
+        contract: None,
+        msg: None,
+    };
+    execute_seilor(&mut app, MINTER, &seilor_token, &mint_msg);
+    assert!(query_refreshed_users(&app, &fund).is_empty());
+
+    // hook enabled
+    update_seilor_config(&creator, &mut app, &seilor_token, &fund, true);
+
+    execute_seilor(&mut app, MINTER, &seilor_token, &mint_msg);
+    assert_eq!(query_refreshed_users(&app, &fund), vec![user_a.clone()]);
+
+    let transfer_msg = ExecuteMsg::Transfer {
+        recipient: USER_B.to_string(),
+        amount: Uint128::from(300u128),
+    };
+    execute_seilor(&mut app, USER_A, &seilor_token, &transfer_msg);
+    assert_eq!(
+        query_refreshed_users(&app, &fund)[1..],
+        [user_a.clone(), user_b.clone()]
+    );
+
+    let send_msg = ExecuteMsg::Send {
+        contract: fund.to_string(),
+        amount: Uint128::from(100u128),
+        msg: Binary::default(),
+    };
+    execute_seilor(&mut app, USER_A, &seilor_token, &send_msg);
+    assert_eq!(
+        query_refreshed_users(&app, &fund)[3..],
+        [user_a.clone(), fund.clone()]
+    );
+
+    let burn_msg = ExecuteMsg::Burn {
+        amount: Uint128::from(100u128),
+        reason: None,
+    };
+    execute_seilor(&mut app, USER_B, &seilor_token, &burn_msg);
+    assert_eq!(query_refreshed_users(&app, &fund).len(), 6);
+    assert_eq!(query_refreshed_users(&app, &fund)[5], user_b);
+
+    // USER_B spends on behalf of USER_A
+    let allowance_msg = ExecuteMsg::IncreaseAllowance {
+        spender: USER_B.to_string(),
+        amount: Uint128::from(600u128),
+        expires: None,
+    };
+    execute_seilor(&mut app, USER_A, &seilor_token, &allowance_msg);
+    assert_eq!(query_refreshed_users(&app, &fund).len(), 6);
+
+    let transfer_from_msg = ExecuteMsg::TransferFrom {
+        owner: USER_A.to_string(),
+        recipient: USER_B.to_string(),
+        amount: Uint128::from(100u128),
+    };
+    execute_seilor(&mut app, USER_B, &seilor_token, &transfer_from_msg);
+    assert_eq!(
+        query_refreshed_users(&app, &fund)[6..],
+        [user_a.clone(), user_b]
+    );
+
+    let send_from_msg = ExecuteMsg::SendFrom {
+        owner: USER_A.to_string(),
+        contract: fund.to_string(),
+        amount: Uint128::from(100u128),
+        msg: Binary::default(),
+    };
+    execute_seilor(&mut app, USER_B, &seilor_token, &send_from_msg);
+    assert_eq!(
+        query_refreshed_users(&app, &fund)[8..],
+        [user_a.clone(), fund.clone()]
+    );
+
+    let burn_from_msg = ExecuteMsg::BurnFrom {
+        owner: USER_A.to_string(),
+        amount: Uint128::from(100u128),
+        reason: None,
+    };
+    execute_seilor(&mut app, USER_B, &seilor_token, &burn_from_msg);
+    assert_eq!(query_refreshed_users(&app, &fund).len(), 11);
+    assert_eq!(query_refreshed_users(&app, &fund)[10], user_a);
+
+    // a self transfer only refreshes the account once
+    let self_transfer_msg = ExecuteMsg::Transfer {
+        recipient: USER_A.to_string(),
+        amount: Uint128::from(100u128),
+    };
+    execute_seilor(&mut app, USER_A, &seilor_token, &self_transfer_msg);
+    assert_eq!(query_refreshed_users(&app, &fund).len(), 12);
+    assert_eq!(query_refreshed_users(&app, &fund)[11], user_a);
+
+    // the tokens held by the contract itself are not refreshed
+    let transfer_locked_msg = ExecuteMsg::TransferLocked {
+        recipient: USER_B.to_string(),
+        amount: Uint128::from(100u128),
+        release_at: mock_env().block.time.seconds() + 100,
+        cancelable: false,
+    };
+    execute_seilor(&mut app, USER_A, &seilor_token, &transfer_locked_msg);
+    assert_eq!(query_refreshed_users(&app, &fund)[12..], [user_a]);
+
+    assert_eq!(
+        get_seilor_balance(&app, &seilor_token, USER_A),
+        Uint128::from(1200u128)
+    );
+    assert_eq!(
+        get_seilor_balance(&app, &seilor_token, USER_B),
+        Uint128::from(300u128)
+    );
+    assert_eq!(
+        get_seilor_balance(&app, &seilor_token, fund.as_str()),
+        Uint128::from(200u128)
+    );
+}
+
+fn query_balance_at(app: &App, seilor_token: &Addr, user: &str, height: u64) -> Uint128 {
+    let res: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            seilor_token.clone(),
+            &QueryMsg::BalanceAt {
+                address: user.to_string(),
+                height,
+            },
+        )
+        .unwrap();
+    res.balance
+}
+
+fn query_total_supply_at(app: &App, seilor_token: &Addr, height: u64) -> Uint128 {
+    let res: TotalSupplyAtResponse = app
+        .wrap()
+        .query_wasm_smart(seilor_token.clone(), &QueryMsg::TotalSupplyAt { height })
+        .unwrap();
+    res.total_supply
+}
+
+#[test]
+fn test_integration_balance_snapshots() {
+    let creator = Addr::unchecked(CREATOR);
+    let mut app = mock_app();
+
+    let seilor_token = seilor_contract_instance(&creator, &mut app);
+    set_seilor_minter(&creator, &mut app, &seilor_token, MINTER);
+    let start_height = app.block_info().height;
+
+    // height + 1: mint 1000 to USER_A
+    app.update_block(|block| block.height += 1);
+    let mint_msg = ExecuteMsg::Mint {
+        recipient: USER_A.to_string(),
+        amount: Uint128::from(1000u128),
+        contract: None,
+        msg: None,
+    };
+    execute_seilor(&mut app, MINTER, &seilor_token, &mint_msg);
+
+    // height + 2: USER_A transfers 400 to USER_B and burns 100
+    app.update_block(|block| block.height += 1);
+    let transfer_msg = ExecuteMsg::Transfer {
+        recipient: USER_B.to_string(),
+        amount: Uint128::from(400u128),
+    };
+    execute_seilor(&mut app, USER_A, &seilor_token, &transfer_msg);
+    let burn_msg = ExecuteMsg::Burn {
+        amount: Uint128::from(100u128),
+        reason: None,
+    };
+    execute_seilor(&mut app, USER_A, &seilor_token, &burn_msg);
+
+    // height + 3: USER_B sends 50 back through an allowance
+    app.update_block(|block| block.height += 1);
+    let allowance_msg = ExecuteMsg::IncreaseAllowance {
+        spender: USER_A.to_string(),
+        amount: Uint128::from(50u128),
+        expires: None,
+    };
+    execute_seilor(&mut app, USER_B, &seilor_token, &allowance_msg);
+    let transfer_from_msg = ExecuteMsg::TransferFrom {
+        owner: USER_B.to_string(),
+        recipient: USER_A.to_string(),
+        amount: Uint128::from(50u128),
+    };
+    execute_seilor(&mut app, USER_A, &seilor_token, &transfer_from_msg);
+    app.update_block(|block| block.height += 1);
+
+    // values are taken at the start of each block
+    let expected = [
+        (start_height + 1, 0u128, 0u128, 0u128),
+        (start_height + 2, 1000u128, 0u128, 1000u128),
+        (start_height + 3, 500u128, 400u128, 900u128),
+        (start_height + 4, 550u128, 350u128, 900u128),
+    ];
+    for (height, balance_a, balance_b, total_supply) in expected {
+        assert_eq!(
+            query_balance_at(&app, &seilor_token, USER_A, height),
+            Uint128::from(balance_a)
+        );
+        assert_eq!(
+            query_balance_at(&app, &seilor_token, USER_B, height),
+            Uint128::from(balance_b)
+        );
+        assert_eq!(
+            query_total_supply_at(&app, &seilor_token, height),
+            Uint128::from(total_supply)
+        );
+    }
+}
+
+fn query_votes_at(app: &App, seilor_token: &Addr, user: &str, height: u64) -> Uint128 {
+    let res: VotesResponse = app
+        .wrap()
+        .query_wasm_smart(
+            seilor_token.clone(),
+            &QueryMsg::VotesAt {
+                address: user.to_string(),
+                height,
+            },
+        )
+        .unwrap();
+    res.votes
+}
+
+fn query_votes(app: &App, seilor_token: &Addr, user: &str) -> Uint128 {
+    let res: VotesResponse = app
+        .wrap()
+        .query_wasm_smart(
+            seilor_token.clone(),
+            &QueryMsg::Votes {
+                address: user.to_string(),
+            },
+        )
+        .unwrap();
+    res.votes
+}
+
+#[test]
+fn test_integration_delegate_votes() {
+    let creator = Addr::unchecked(CREATOR);
+    let mut app = mock_app();
+
+    let seilor_token = seilor_contract_instance(&creator, &mut app);
+    set_seilor_minter(&creator, &mut app, &seilor_token, MINTER);
+    let start_height = app.block_info().height;
+
+    // height + 1: USER_A gets 1000 and delegates to USER_C, USER_B delegates to itself
+    app.update_block(|block| block.height += 1);
+    let mint_msg = ExecuteMsg::Mint {
+        recipient: USER_A.to_string(),
+        amount: Uint128::from(1000u128),
+        contract: None,
+        msg: None,
+    };
+    execute_seilor(&mut app, MINTER, &seilor_token, &mint_msg);
+    let delegate_msg = ExecuteMsg::Delegate {
+        delegatee: USER_C.to_string(),
+    };
+    execute_seilor(&mut app, USER_A, &seilor_token, &delegate_msg);
+    let delegate_msg = ExecuteMsg::Delegate {
+        delegatee: USER_B.to_string(),
+    };
+    execute_seilor(&mut app, USER_B, &seilor_token, &delegate_msg);
+    assert_eq!(
+        query_votes(&app, &seilor_token, USER_C),
+        Uint128::from(1000u128)
+    );
+
+    // height + 2: votes follow transfers and burns
+    app.update_block(|block| block.height += 1);
+    let transfer_msg = ExecuteMsg::Transfer {
+        recipient: USER_B.to_string(),
+        amount: Uint128::from(300u128),
+    };
+    execute_seilor(&mut app, USER_A, &seilor_token, &transfer_msg);
+    let burn_msg = ExecuteMsg::Burn {
+        amount: Uint128::from(100u128),
+        reason: None,
+    };
+    execute_seilor(&mut app, USER_A, &seilor_token, &burn_msg);
+    execute_seilor(&mut app, MINTER, &seilor_token, &mint_msg);
+
+    // height + 3: USER_A moves its delegation to USER_B
+    app.update_block(|block| block.height += 1);
+    let delegate_msg = ExecuteMsg::Delegate {
+        delegatee: USER_B.to_string(),
+    };
+    execute_seilor(&mut app, USER_A, &seilor_token, &delegate_msg);
+    app.update_block(|block| block.height += 1);
+
+    let expected = [
+        (start_height + 1, 0u128, 0u128, 0u128),
+        (start_height + 2, 0u128, 0u128, 1000u128),
+        (start_height + 3, 0u128, 300u128, 1600u128),
+        (start_height + 4, 0u128, 1900u128, 0u128),
+    ];
+    for (height, votes_a, votes_b, votes_c) in expected {
+        assert_eq!(
+            query_votes_at(&app, &seilor_token, USER_A, height),
+            Uint128::from(votes_a)
+        );
+        assert_eq!(
+            query_votes_at(&app, &seilor_token, USER_B, height),
+            Uint128::from(votes_b)
+        );
+        assert_eq!(
+            query_votes_at(&app, &seilor_token, USER_C, height),
+            Uint128::from(votes_c)
+        );
+    }
+
+    let res: DelegationResponse = app
+        .wrap()
+        .query_wasm_smart(
+            seilor_token.clone(),
+            &QueryMsg::Delegation {
+                delegator: USER_A.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(res.delegatee, Some(Addr::unchecked(USER_B)));
+}
+
+fn query_mint_escrow(app: &App, seilor_token: &Addr, user: &str) -> Uint128 {
+    let res: MintEscrowResponse = app
+        .wrap()
+        .query_wasm_smart(
+            seilor_token.clone(),
+            &QueryMsg::MintEscrow {
+                address: user.to_string(),
+            },
+        )
+        .unwrap();
+    res.amount
+}
+
+#[test]
+fn test_integration_mint_hook_failure() {
+    let creator = Addr::unchecked(CREATOR);
+    let mut app = mock_app();
+
+    let seilor_token = seilor_contract_instance(&creator, &mut app);
+    let fund = fund_contract_instance(&creator, &mut app);
+    set_seilor_minter(&creator, &mut app, &seilor_token, MINTER);
+    update_seilor_config(&creator, &mut app, &seilor_token, &fund, true);
+
+    // the fund takes the hook, the tokens stay with the recipient
+    let mint_msg = ExecuteMsg::Mint {
+        recipient: USER_A.to_string(),
+        amount: Uint128::from(1000u128),
+        contract: Some(fund.to_string()),
+        msg: Some(Binary::from(b"{}".to_vec())),
+    };
+    execute_seilor(&mut app, MINTER, &seilor_token, &mint_msg);
+    assert_eq!(
+        get_seilor_balance(&app, &seilor_token, USER_A),
+        Uint128::from(1000u128)
+    );
+
+    // USER_B is no contract, the mint goes through and the tokens are held for USER_B
+    let mint_msg = ExecuteMsg::Mint {
+        recipient: USER_B.to_string(),
+        amount: Uint128::from(400u128),
+        contract: Some(USER_B.to_string()),
+        msg: Some(Binary::from(b"{}".to_vec())),
+    };
+    let refreshed = query_refreshed_balances(&app, &fund).len();
+    execute_seilor(&mut app, MINTER, &seilor_token, &mint_msg);
+    assert_eq!(
+        get_seilor_balance(&app, &seilor_token, USER_B),
+        Uint128::zero()
+    );
+    // USER_B is refreshed once, after the tokens went into the escrow
+    assert_eq!(
+        query_refreshed_balances(&app, &fund)[refreshed..],
+        [(Addr::unchecked(USER_B), Uint128::zero())]
+    );
+    assert_eq!(
+        get_seilor_balance(&app, &seilor_token, seilor_token.as_str()),
+        Uint128::from(400u128)
+    );
+    assert_eq!(
+        query_mint_escrow(&app, &seilor_token, USER_B),
+        Uint128::from(400u128)
+    );
+
+    let res: MintHookFailuresResponse = app
+        .wrap()
+        .query_wasm_smart(
+            seilor_token.clone(),
+            &QueryMsg::MintHookFailures {
+                recipient: Some(USER_B.to_string()),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(res.failures.len(), 1);
+    assert_eq!(res.failures[0].mint_id, 2);
+    assert_eq!(res.failures[0].minter, Addr::unchecked(MINTER));
+    assert_eq!(res.failures[0].contract, Addr::unchecked(USER_B));
+    assert_eq!(res.failures[0].amount, Uint128::from(400u128));
+
+    execute_seilor(
+        &mut app,
+        USER_B,
+        &seilor_token,
+        &ExecuteMsg::ClaimMintEscrow {},
+    );
+    assert_eq!(
+        get_seilor_balance(&app, &seilor_token, USER_B),
+        Uint128::from(400u128)
+    );
+    assert_eq!(
+        query_mint_escrow(&app, &seilor_token, USER_B),
+        Uint128::zero()
+    );
+    let res = app.execute_contract(
+        Addr::unchecked(USER_B),
+        seilor_token.clone(),
+        &ExecuteMsg::ClaimMintEscrow {},
+        &[],
+    );
+    assert!(res.is_err());
+
+    // a vesting recipient doesn't make the escrow revert the mint
+    let vesting_msg = ExecuteMsg::CreateVesting {
+        recipient: USER_C.to_string(),
+        amount: Uint128::from(500u128),
+        schedule: VestingSchedule::Cliff {
+            release_at: mock_env().block.time.seconds() + 1000,
+        },
+    };
+    execute_seilor(&mut app, MINTER, &seilor_token, &vesting_msg);
+    let mint_msg = ExecuteMsg::Mint {
+        recipient: USER_C.to_string(),
+        amount: Uint128::from(300u128),
+        contract: Some(USER_C.to_string()),
+        msg: Some(Binary::from(b"{}".to_vec())),
+    };
+    execute_seilor(&mut app, MINTER, &seilor_token, &mint_msg);
+    assert_eq!(
+        get_seilor_balance(&app, &seilor_token, USER_C),
+        Uint128::from(500u128)
+    );
+    assert_eq!(
+        query_mint_escrow(&app, &seilor_token, USER_C),
+        Uint128::from(300u128)
+    );
+}
+
+type TokenFactoryApp =
+    App<BankKeeper, MockApi, MockStorage, MockTokenFactory, WasmKeeper<MockTokenFactoryMsg, Empty>>;
+
+// the chain build sends the token-factory messages as stargate messages, which multi-test
+// can't run, so the wrap handlers are instantiated with the mock token factory's messages
+fn execute_with_token_factory(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response<MockTokenFactoryMsg>, ContractError> {
+    match msg {
+        ExecuteMsg::CreateWrapDenom { subdenom } => create_wrap_denom(deps, env, info, subdenom),
+        ExecuteMsg::Wrap { amount } => wrap(deps, env, info, amount),
+        ExecuteMsg::Unwrap {} => unwrap(deps, env, info),
+        _ => Ok(into_custom_response(execute(deps, env, info, msg)?)?),
+    }
+}
+
+fn instantiate_with_token_factory(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response<MockTokenFactoryMsg>, ContractError> {
+    Ok(into_custom_response(instantiate(deps, env, info, msg)?)?)
+}
+
+fn query_wrapped_supply(app: &TokenFactoryApp, seilor_token: &Addr) -> WrappedSupplyResponse {
+    app.wrap()
+        .query_wasm_smart(seilor_token.clone(), &QueryMsg::WrappedSupply {})
+        .unwrap()
+}
+
+fn query_token_factory_balance(app: &TokenFactoryApp, seilor_token: &Addr, user: &str) -> Uint128 {
+    let res: BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            seilor_token.clone(),
+            &QueryMsg::Balance {
+                address: user.to_string(),
+            },
+        )
+        .unwrap();
+    res.balance
+}
+
+#[test]
+fn test_integration_wrap() {
+    let mut app: TokenFactoryApp = AppBuilder::new_custom()
+        .with_custom(MockTokenFactory {})
+        .with_block(mock_env().block)
+        .build(|_, _, _| {});
+    let seilor_contract = Box::new(ContractWrapper::new(
+        execute_with_token_factory,
+        instantiate_with_token_factory,
+        query,
+    ));
+    let seilor_code_id = app.store_code(seilor_contract);
+    let seilor_token = app
+        .instantiate_contract(
+            seilor_code_id,
+            Addr::unchecked(CREATOR),
+            &mock_instantiate_msg(),
+            &[],
+            String::from("SEILOR"),
+            None,
+        )
+        .unwrap();
+    let set_minter_msg = ExecuteMsg::SetMinter {
+        minter: MINTER.to_string(),
+        quota: Uint128::from(MAX_SUPPLY),
+    };
+    app.execute_contract(
+        Addr::unchecked(CREATOR),
+        seilor_token.clone(),
+        &set_minter_msg,
+        &[],
+    )
+    .unwrap();
+    let mint_msg = ExecuteMsg::Mint {
+        recipient: USER_A.to_string(),
+        amount: Uint128::from(1000u128),
+        contract: None,
+        msg: None,
+    };
+    app.execute_contract(
+        Addr::unchecked(MINTER),
+        seilor_token.clone(),
+        &mint_msg,
+        &[],
+    )
+    .unwrap();
+
+    // nothing to wrap into before gov creates the denom
+    let wrap_msg = ExecuteMsg::Wrap {
+        amount: Uint128::from(600u128),
+    };
+    let res = app.execute_contract(
+        Addr::unchecked(USER_A),
+        seilor_token.clone(),
+        &wrap_msg,
+        &[],
+    );
+    assert!(res.is_err());
+
+    let create_denom_msg = ExecuteMsg::CreateWrapDenom {
+        subdenom: "seilor".to_string(),
+    };
+    app.execute_contract(
+        Addr::unchecked(CREATOR),
+        seilor_token.clone(),
+        &create_denom_msg,
+        &[],
+    )
+    .unwrap();
+    let denom = format!("factory/{}/seilor", seilor_token);
+
+    app.execute_contract(
+        Addr::unchecked(USER_A),
+        seilor_token.clone(),
+        &wrap_msg,
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        query_token_factory_balance(&app, &seilor_token, USER_A),
+        Uint128::from(400u128)
+    );
+    assert_eq!(
+        query_token_factory_balance(&app, &seilor_token, seilor_token.as_str()),
+        Uint128::from(600u128)
+    );
+    assert_eq!(
+        app.wrap().query_balance(USER_A, &denom).unwrap().amount,
+        Uint128::from(600u128)
+    );
+    assert_eq!(
+        query_wrapped_supply(&app, &seilor_token),
+        WrappedSupplyResponse {
+            denom: Some(denom.clone()),
+            wrapped_supply: Uint128::from(600u128),
+        }
+    );
+
+    // USER_B unwraps what USER_A sent over natively
+    app.send_tokens(
+        Addr::unchecked(USER_A),
+        Addr::unchecked(USER_B),
+        &coins(250, &denom),
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked(USER_B),
+        seilor_token.clone(),
+        &ExecuteMsg::Unwrap {},
+        &coins(250, &denom),
+    )
+    .unwrap();
+    assert_eq!(
+        query_token_factory_balance(&app, &seilor_token, USER_B),
+        Uint128::from(250u128)
+    );
+    assert_eq!(
+        app.wrap().query_balance(USER_B, &denom).unwrap().amount,
+        Uint128::zero()
+    );
+    assert_eq!(
+        app.wrap().query_balance(USER_A, &denom).unwrap().amount,
+        Uint128::from(350u128)
+    );
+    assert_eq!(
+        query_wrapped_supply(&app, &seilor_token).wrapped_supply,
+        Uint128::from(350u128)
+    );
+}
@@ -0,0 +1,26 @@
+use crate::msg::InstantiateMsg;
+use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
+
+pub const CREATOR: &str = "creator";
+pub const MINTER: &str = "minter";
+pub const USER_A: &str = "user_a";
+pub const USER_B: &str = "user_b";
+pub const USER_C: &str = "user_c";
+
+pub const MAX_SUPPLY: u128 = 1_000_000_000_000_000u128;
+
+pub fn mock_instantiate_msg() -> InstantiateMsg {
+    let cw20_init_msg = Cw20InstantiateMsg {
+        name: "seilor dev".to_string(),
+        symbol: "seilor".to_string(),
+        decimals: 6,
+        initial_balances: vec![],
+        mint: None,
+        marketing: None,
+    };
+    InstantiateMsg {
+        cw20_init_msg,
+        max_supply: MAX_SUPPLY,
+        gov: None,
+    }
+}
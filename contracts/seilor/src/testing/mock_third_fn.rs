@@ -0,0 +1,188 @@
+use crate::mint_receiver::Cw20MintReceiveMsg;
+use crate::token_factory::TokenFactory;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_binary, Addr, Api, BankMsg, Binary, BlockInfo, Coin, CosmosMsg, CustomMsg, CustomQuery,
+    Deps, DepsMut, Empty, Env, MessageInfo, Querier, Response, StdError, StdResult, Storage,
+    Uint128,
+};
+use cw20::{BalanceResponse, Cw20QueryMsg, Cw20ReceiveMsg};
+use cw_multi_test::{AppResponse, BankSudo, Contract, ContractWrapper, CosmosRouter, Module};
+use cw_storage_plus::Item;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+
+// every user the mock fund was asked to refresh, in call order
+const REFRESHED_USERS: Item<Vec<Addr>> = Item::new("refreshed_users");
+
+// the token balance each refreshed user had when the refresh arrived
+const REFRESHED_BALANCES: Item<Vec<(Addr, Uint128)>> = Item::new("refreshed_balances");
+
+// mirrors SeilorFundMsg, plus cw20 receivers so the fund can be the target of Send and mint hooks
+#[cw_serde]
+pub enum MockFundExecuteMsg {
+    RefreshReward { user: Addr },
+    Receive(Cw20ReceiveMsg),
+    MintReceive(Cw20MintReceiveMsg),
+}
+
+#[cw_serde]
+pub enum MockFundQueryMsg {
+    RefreshedUsers {},
+    RefreshedBalances {},
+}
+
+fn mock_fund_instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response> {
+    REFRESHED_USERS.save(deps.storage, &vec![])?;
+    REFRESHED_BALANCES.save(deps.storage, &vec![])?;
+    Ok(Response::default())
+}
+
+fn mock_fund_execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: MockFundExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        MockFundExecuteMsg::RefreshReward { user } => {
+            // the refresh comes from the token contract
+            let balance: BalanceResponse = deps.querier.query_wasm_smart(
+                info.sender,
+                &Cw20QueryMsg::Balance {
+                    address: user.to_string(),
+                },
+            )?;
+            let mut balances = REFRESHED_BALANCES.load(deps.storage)?;
+            balances.push((user.clone(), balance.balance));
+            REFRESHED_BALANCES.save(deps.storage, &balances)?;
+
+            let mut users = REFRESHED_USERS.load(deps.storage)?;
+            users.push(user);
+            REFRESHED_USERS.save(deps.storage, &users)?;
+            Ok(Response::default())
+        }
+        MockFundExecuteMsg::Receive(_) | MockFundExecuteMsg::MintReceive(_) => {
+            Ok(Response::default())
+        }
+    }
+}
+
+fn mock_fund_query(deps: Deps, _env: Env, msg: MockFundQueryMsg) -> StdResult<Binary> {
+    match msg {
+        MockFundQueryMsg::RefreshedUsers {} => to_binary(&REFRESHED_USERS.load(deps.storage)?),
+        MockFundQueryMsg::RefreshedBalances {} => {
+            to_binary(&REFRESHED_BALANCES.load(deps.storage)?)
+        }
+    }
+}
+
+pub fn mock_fund_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(
+        mock_fund_execute,
+        mock_fund_instantiate,
+        mock_fund_query,
+    ))
+}
+
+// the token-factory subset of the Sei wasm bindings, handled by MockTokenFactory
+#[cw_serde]
+pub enum MockTokenFactoryMsg {
+    CreateDenom { subdenom: String },
+    MintTokens { amount: Coin },
+    BurnTokens { amount: Coin },
+}
+
+impl CustomMsg for MockTokenFactoryMsg {}
+
+impl TokenFactory for MockTokenFactoryMsg {
+    fn create_denom(_sender: &Addr, subdenom: &str) -> CosmosMsg<Self> {
+        CosmosMsg::Custom(MockTokenFactoryMsg::CreateDenom {
+            subdenom: subdenom.to_string(),
+        })
+    }
+
+    fn mint_tokens(_sender: &Addr, amount: &Coin) -> CosmosMsg<Self> {
+        CosmosMsg::Custom(MockTokenFactoryMsg::MintTokens {
+            amount: amount.clone(),
+        })
+    }
+
+    fn burn_tokens(_sender: &Addr, amount: &Coin) -> CosmosMsg<Self> {
+        CosmosMsg::Custom(MockTokenFactoryMsg::BurnTokens {
+            amount: amount.clone(),
+        })
+    }
+}
+
+// stands in for the chain's token-factory module, denoms are not tracked so any denom
+// can be minted and burned by anyone
+pub struct MockTokenFactory {}
+
+impl Module for MockTokenFactory {
+    type ExecT = MockTokenFactoryMsg;
+    type QueryT = Empty;
+    type SudoT = Empty;
+
+    fn execute<ExecC, QueryC>(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        sender: Addr,
+        msg: MockTokenFactoryMsg,
+    ) -> anyhow::Result<AppResponse>
+    where
+        ExecC: std::fmt::Debug + Clone + PartialEq + JsonSchema + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        match msg {
+            MockTokenFactoryMsg::CreateDenom { .. } => Ok(AppResponse::default()),
+            MockTokenFactoryMsg::MintTokens { amount } => {
+                let mint = BankSudo::Mint {
+                    to_address: sender.to_string(),
+                    amount: vec![amount],
+                };
+                router.sudo(api, storage, block, mint.into())
+            }
+            MockTokenFactoryMsg::BurnTokens { amount } => {
+                let burn = BankMsg::Burn {
+                    amount: vec![amount],
+                };
+                router.execute(api, storage, block, sender, burn.into())
+            }
+        }
+    }
+
+    fn sudo<ExecC, QueryC>(
+        &self,
+        _api: &dyn Api,
+        _storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        _block: &BlockInfo,
+        _msg: Empty,
+    ) -> anyhow::Result<AppResponse>
+    where
+        ExecC: std::fmt::Debug + Clone + PartialEq + JsonSchema + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        Err(StdError::generic_err("Unsupported token factory sudo").into())
+    }
+
+    fn query(
+        &self,
+        _api: &dyn Api,
+        _storage: &dyn Storage,
+        _querier: &dyn Querier,
+        _block: &BlockInfo,
+        _request: Empty,
+    ) -> anyhow::Result<Binary> {
+        Err(StdError::generic_err("Unsupported token factory query").into())
+    }
+}
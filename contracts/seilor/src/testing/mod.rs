@@ -1 +1,4 @@
-mod tests;
\ No newline at end of file
+mod integration;
+mod mock_fn;
+mod mock_third_fn;
+mod tests;
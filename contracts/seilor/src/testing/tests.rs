@@ -1,17 +1,36 @@
 #[cfg(test)]
 mod tests {
-    use crate::contract::{execute, instantiate};
-    use crate::msg::{ExecuteMsg, InstantiateMsg, SeilorConfigResponse};
-    use crate::querier::query_seilor_config;
+    use crate::contract::{execute, instantiate, migrate};
+    use crate::error::ContractError;
+    use crate::helper::pubkey_to_address;
+    use crate::msg::{
+        EmissionCurve, EmissionReceiver, EmissionSegment, ExecuteMsg, InstantiateMsg, MigrateMsg, MinterInfoResponse, PermitMsg, RecoverAsset,
+        SeilorConfigResponse, VestingSchedule,
+    };
+    use crate::querier::{
+        query_burn_history, query_burn_stats, query_circulating_supply, query_escrow,
+        query_escrows_by_recipient, query_escrows_by_sender, query_frozen_accounts, query_emission_schedule, query_launch_limits, query_mint_history, query_mint_proposal, query_mint_proposals, query_payments_received, query_periodic_allowance, query_projected_supply, query_vesting_balance, query_wrapped_supply, query_minters, query_seilor_config, query_simulate_transfer,
+        query_permit_nonce, query_stream, query_streams_by_recipient, query_top_holders,
+        query_transfer_tax, query_votes,
+    };
+    use crate::state::{store_seilor_config, LegacySeilorConfig, SeilorConfig};
     use cosmwasm_std::testing::{
-        mock_dependencies, mock_dependencies_with_balance, mock_env, mock_info,
+        mock_dependencies, mock_dependencies_with_balance, mock_env, mock_info, MockApi,
+        MockQuerier, MockStorage,
     };
     use cosmwasm_std::StdError::GenericErr;
-    use cosmwasm_std::{coins, Addr, Deps, Response, Uint128};
-    use cw20_base::contract::query_balance;
+    use cosmwasm_std::{
+        coins, from_binary, to_vec, Addr, BankMsg, Binary, CosmosMsg, Deps, OwnedDeps, Response, Storage,
+        Uint128, WasmMsg,
+    };
+    use cw20::{Cw20Coin, Cw20ExecuteMsg};
+    use cw20_base::allowances::query_allowance;
+    use cw20_base::contract::{instantiate as cw20_instantiate, query_balance, query_token_info};
     use cw20_base::msg::InstantiateMarketingInfo;
     use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
-    use cw20_base::ContractError;
+    use cw20_base::state::TOKEN_INFO;
+    use k256::ecdsa::signature::Signer;
+    use k256::ecdsa::{Signature, SigningKey};
 
     fn get_balance<T: Into<String>>(deps: Deps, address: T) -> Uint128 {
         query_balance(deps, address.into()).unwrap().balance
@@ -63,7 +82,10 @@ mod tests {
                 max_supply,
                 gov: Addr::unchecked("gov"),
                 fund: Addr::unchecked(""),
-                distribute: Addr::unchecked(""),
+                refresh_reward_enabled: false,
+                mint_to_frozen_enabled: false,
+                total_supply: Uint128::zero(),
+                remaining: Uint128::from(max_supply),
             }
         );
     }
@@ -82,8 +104,9 @@ mod tests {
         // Negative test case with insufficient permissions
         let _msg = ExecuteMsg::UpdateConfig {
             fund: Some(Addr::unchecked("new_fund")),
-            distribute: Some(Addr::unchecked("new_distribute")),
             gov: Some(Addr::unchecked("new_gov")),
+            refresh_reward_enabled: None,
+            mint_to_frozen_enabled: None,
         };
         let _info = mock_info("random_user", &[]);
         let _res = execute(deps.as_mut(), mock_env(), _info, _msg);
@@ -99,15 +122,19 @@ mod tests {
                 max_supply,
                 gov: Addr::unchecked("creator"),
                 fund: Addr::unchecked(""),
-                distribute: Addr::unchecked(""),
+                refresh_reward_enabled: false,
+                mint_to_frozen_enabled: false,
+                total_supply: Uint128::zero(),
+                remaining: Uint128::from(max_supply),
             }
         );
 
         // Positive test case
         let _msg = ExecuteMsg::UpdateConfig {
             fund: Some(Addr::unchecked("new_fund")),
-            distribute: Some(Addr::unchecked("new_distribute")),
             gov: Some(Addr::unchecked("new_gov")),
+            refresh_reward_enabled: None,
+            mint_to_frozen_enabled: None,
         };
         let _info = mock_info("creator", &[]);
         let _res = execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
@@ -120,15 +147,19 @@ mod tests {
                 max_supply,
                 gov: Addr::unchecked("new_gov"),
                 fund: Addr::unchecked("new_fund"),
-                distribute: Addr::unchecked("new_distribute"),
+                refresh_reward_enabled: false,
+                mint_to_frozen_enabled: false,
+                total_supply: Uint128::zero(),
+                remaining: Uint128::from(max_supply),
             }
         );
 
         // Verify old gov with insufficient permissions
         let _msg = ExecuteMsg::UpdateConfig {
             fund: Some(Addr::unchecked("new_fund")),
-            distribute: Some(Addr::unchecked("new_distribute")),
             gov: Some(Addr::unchecked("new_gov")),
+            refresh_reward_enabled: None,
+            mint_to_frozen_enabled: None,
         };
         let _info = mock_info("creator", &[]);
         let _res = execute(deps.as_mut(), mock_env(), _info, _msg);
@@ -150,6 +181,7 @@ mod tests {
         let _res = instantiate(deps.as_mut(), mock_env(), _info, instantiate_msg).unwrap();
         assert_eq!(0, _res.messages.len());
 
+        // no minter registered yet
         let _msg = ExecuteMsg::Mint {
             recipient: "lucky".to_string(),
             amount,
@@ -157,46 +189,53 @@ mod tests {
             msg: None,
         };
         let _info = mock_info("creator", &[]);
-        let _res = execute(deps.as_mut(), mock_env(), _info, _msg).unwrap_err();
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg);
         match _res {
-            ContractError::Std(GenericErr { msg, .. }) => {
-                assert_eq!(
-                    msg,
-                    "Fund or distribute contract must to be configured".to_string()
-                )
-            }
-            _ => panic!("Do not enter in"),
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
         }
 
-        let _msg = ExecuteMsg::Mint {
-            recipient: "lucky".to_string(),
-            amount,
-            contract: None,
-            msg: None,
+        // Negative test case with insufficient permissions, only gov can register minters
+        let _msg = ExecuteMsg::SetMinter {
+            minter: "new_fund".to_string(),
+            quota: amount,
         };
         let _info = mock_info("random_user", &[]);
-        let _res = execute(deps.as_mut(), mock_env(), _info, _msg).unwrap_err();
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg);
         match _res {
-            ContractError::Std(GenericErr { msg, .. }) => {
-                assert_eq!(
-                    msg,
-                    "Fund or distribute contract must to be configured".to_string()
-                )
-            }
-            _ => panic!("Do not enter in"),
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
         }
 
+        // register minters
+        let _msg = ExecuteMsg::SetMinter {
+            minter: "new_fund".to_string(),
+            quota: amount,
+        };
+        let _info = mock_info("creator", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        assert_eq!(0, _res.messages.len());
+
+        let _msg = ExecuteMsg::SetMinter {
+            minter: "new_distribute".to_string(),
+            quota: Uint128::from(200000u128),
+        };
+        let _info = mock_info("creator", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        assert_eq!(0, _res.messages.len());
+
         // proper update config
         let _msg = ExecuteMsg::UpdateConfig {
             fund: Some(Addr::unchecked("new_fund".to_string())),
-            distribute: Some(Addr::unchecked("new_distribute".to_string())),
             gov: Some(Addr::unchecked("new_gov".to_string())),
+            refresh_reward_enabled: None,
+            mint_to_frozen_enabled: None,
         };
         let _info = mock_info("creator", &[]);
         let _res = execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
         assert_eq!(0, _res.messages.len());
 
-        // Negative test case with insufficient permissions, only seilor_fund && seilor_distribute
+        // Negative test case with insufficient permissions, only registered minters
         let _msg = ExecuteMsg::Mint {
             recipient: "lucky".to_string(),
             amount,
@@ -223,7 +262,7 @@ mod tests {
             _ => panic!("Must return unauthorized error"),
         }
 
-        // Positive test case, only seilor_fund && seilor_distribute
+        // Positive test case, only registered minters
         let _msg = ExecuteMsg::Mint {
             recipient: "lucky".to_string(),
             amount,
@@ -247,6 +286,269 @@ mod tests {
         assert_eq!(0, _res.messages.len());
 
         assert_eq!(get_balance(deps.as_ref(), "lucky"), Uint128::new(224466));
+
+        // new_fund has used up its quota
+        let _msg = ExecuteMsg::Mint {
+            recipient: "lucky".to_string(),
+            amount: Uint128::one(),
+            contract: None,
+            msg: None,
+        };
+        let _info = mock_info("new_fund", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg).unwrap_err();
+        match _res {
+            ContractError::Std(GenericErr { msg, .. }) => {
+                assert_eq!(msg, "Minter quota exceeded".to_string())
+            }
+            _ => panic!("Do not enter in"),
+        }
+
+        // an amount overflowing the minted counter fails instead of panicking
+        let _msg = ExecuteMsg::Mint {
+            recipient: "lucky".to_string(),
+            amount: Uint128::MAX,
+            contract: None,
+            msg: None,
+        };
+        let _info = mock_info("new_fund", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg).unwrap_err();
+        match _res {
+            ContractError::OverflowError(_) => {}
+            _ => panic!("Must return overflow error"),
+        }
+
+        // a new quota keeps the minted counter
+        let _msg = ExecuteMsg::SetMinter {
+            minter: "new_fund".to_string(),
+            quota: Uint128::from(200000u128),
+        };
+        let _info = mock_info("new_gov", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+
+        let _res = query_minters(deps.as_ref(), None, None).unwrap();
+        assert_eq!(
+            _res.minters,
+            vec![
+                MinterInfoResponse {
+                    minter: Addr::unchecked("new_distribute"),
+                    quota: Uint128::from(200000u128),
+                    minted: amount,
+                    remaining: Uint128::from(87767u128),
+                },
+                MinterInfoResponse {
+                    minter: Addr::unchecked("new_fund"),
+                    quota: Uint128::from(200000u128),
+                    minted: amount,
+                    remaining: Uint128::from(87767u128),
+                },
+            ]
+        );
+        let _res =
+            query_minters(deps.as_ref(), Some("new_distribute".to_string()), Some(1)).unwrap();
+        assert_eq!(_res.minters.len(), 1);
+        assert_eq!(_res.minters[0].minter, Addr::unchecked("new_fund"));
+
+        // removed minters lose the mint permission
+        let _msg = ExecuteMsg::RemoveMinter {
+            minter: "new_fund".to_string(),
+        };
+        let _info = mock_info("new_gov", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::Mint {
+            recipient: "lucky".to_string(),
+            amount: Uint128::one(),
+            contract: None,
+            msg: None,
+        };
+        let _info = mock_info("new_fund", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg);
+        match _res {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+    }
+
+    #[test]
+    fn test_migrate_legacy_minters() {
+        let mut deps = mock_dependencies();
+        let max_supply = 1000000u128;
+
+        let instantiate_msg = default_instantiate(max_supply);
+        let _info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), mock_env(), _info, instantiate_msg).unwrap();
+
+        // config as stored by the fund/distribute release
+        let legacy_config = LegacySeilorConfig {
+            max_supply,
+            fund: Addr::unchecked("old_fund"),
+            gov: Addr::unchecked("creator"),
+            distribute: Some(Addr::unchecked("old_distribute")),
+            refresh_reward_enabled: false,
+        };
+        deps.storage
+            .set(b"seilor_config", &to_vec(&legacy_config).unwrap());
+
+        let _msg = MigrateMsg {
+            fund_quota: None,
+            distribute_quota: Some(Uint128::from(5000u128)),
+        };
+        migrate(deps.as_mut(), mock_env(), _msg).unwrap();
+
+        let _res = query_minters(deps.as_ref(), None, None).unwrap();
+        assert_eq!(
+            _res.minters,
+            vec![
+                MinterInfoResponse {
+                    minter: Addr::unchecked("old_distribute"),
+                    quota: Uint128::from(5000u128),
+                    minted: Uint128::zero(),
+                    remaining: Uint128::from(5000u128),
+                },
+                MinterInfoResponse {
+                    minter: Addr::unchecked("old_fund"),
+                    quota: Uint128::from(max_supply),
+                    minted: Uint128::zero(),
+                    remaining: Uint128::from(max_supply),
+                },
+            ]
+        );
+        assert_eq!(
+            query_seilor_config(deps.as_ref()).unwrap(),
+            SeilorConfigResponse {
+                max_supply,
+                gov: Addr::unchecked("creator"),
+                fund: Addr::unchecked("old_fund"),
+                refresh_reward_enabled: false,
+                mint_to_frozen_enabled: false,
+                total_supply: Uint128::zero(),
+                remaining: Uint128::from(max_supply),
+            }
+        );
+
+        let _msg = ExecuteMsg::Mint {
+            recipient: "lucky".to_string(),
+            amount: Uint128::from(5000u128),
+            contract: None,
+            msg: None,
+        };
+        let _info = mock_info("old_distribute", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "lucky"), Uint128::new(5000));
+
+        // a removed legacy minter is not registered again by a later migrate
+        let _msg = ExecuteMsg::RemoveMinter {
+            minter: "old_fund".to_string(),
+        };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+
+        let _msg = MigrateMsg {
+            fund_quota: None,
+            distribute_quota: None,
+        };
+        let _res = migrate(deps.as_mut(), mock_env(), _msg).unwrap();
+        assert_eq!(_res.attributes.len(), 1);
+
+        let _res = query_minters(deps.as_ref(), None, None).unwrap();
+        assert_eq!(_res.minters.len(), 1);
+        assert_eq!(_res.minters[0].minter, Addr::unchecked("old_distribute"));
+        assert_eq!(_res.minters[0].minted, Uint128::from(5000u128));
+    }
+
+    #[test]
+    fn test_seed_holders() {
+        let mut deps = mock_dependencies();
+        let max_supply = 1000000u128;
+        let top_holders = |deps: Deps| -> Vec<(String, u128)> {
+            query_top_holders(deps, None, None)
+                .unwrap()
+                .holders
+                .into_iter()
+                .map(|holder| (holder.address.to_string(), holder.balance.u128()))
+                .collect()
+        };
+
+        // balances as stored by a release without checkpoints
+        let mut cw20_init_msg = mock_cw20_init_msg();
+        cw20_init_msg.initial_balances = vec![
+            Cw20Coin {
+                address: "alice".to_string(),
+                amount: Uint128::from(100u128),
+            },
+            Cw20Coin {
+                address: "bob".to_string(),
+                amount: Uint128::from(200u128),
+            },
+            Cw20Coin {
+                address: "carol".to_string(),
+                amount: Uint128::from(300u128),
+            },
+        ];
+        let _info = mock_info("creator", &[]);
+        cw20_instantiate(deps.as_mut(), mock_env(), _info, cw20_init_msg).unwrap();
+        let seilor_config = SeilorConfig {
+            max_supply,
+            fund: Addr::unchecked(""),
+            gov: Addr::unchecked("creator"),
+            refresh_reward_enabled: false,
+            mint_to_frozen_enabled: false,
+        };
+        store_seilor_config(deps.as_mut().storage, &seilor_config).unwrap();
+
+        // migrate leaves the holders to SeedHolders
+        let _msg = MigrateMsg {
+            fund_quota: None,
+            distribute_quota: None,
+        };
+        let _res = migrate(deps.as_mut(), mock_env(), _msg).unwrap();
+        assert_eq!(_res.attributes[1].value, "pending");
+        assert!(top_holders(deps.as_ref()).is_empty());
+
+        let _msg = ExecuteMsg::SeedHolders { limit: Some(2) };
+        let _info = mock_info("alice", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg);
+        match _res {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+
+        // a holder that delegates before being seeded isn't counted twice
+        let _msg = ExecuteMsg::Delegate {
+            delegatee: "dave".to_string(),
+        };
+        let _info = mock_info("bob", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::SeedHolders { limit: Some(2) };
+        let _info = mock_info("creator", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        assert_eq!(_res.attributes[2].value, "false");
+        assert_eq!(
+            top_holders(deps.as_ref()),
+            vec![("bob".to_string(), 200), ("alice".to_string(), 100)]
+        );
+        assert_eq!(
+            query_votes(deps.as_ref(), "dave".to_string()).unwrap().votes,
+            Uint128::new(200)
+        );
+
+        let _msg = ExecuteMsg::SeedHolders { limit: Some(2) };
+        let _info = mock_info("creator", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        assert_eq!(_res.attributes[1].value, "1");
+        assert_eq!(_res.attributes[2].value, "true");
+        assert_eq!(top_holders(deps.as_ref()).len(), 3);
+
+        let _msg = ExecuteMsg::SeedHolders { limit: None };
+        let _info = mock_info("creator", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg);
+        match _res.unwrap_err() {
+            ContractError::Std(GenericErr { msg, .. }) => {
+                assert_eq!(msg, "No holders left to seed")
+            }
+            _ => panic!("Must return generic error"),
+        }
     }
 
     #[test]
@@ -264,8 +566,17 @@ mod tests {
         // proper update config
         let _msg = ExecuteMsg::UpdateConfig {
             fund: Some(Addr::unchecked("new_fund".to_string())),
-            distribute: Some(Addr::unchecked("new_distribute".to_string())),
             gov: Some(Addr::unchecked("creator".to_string())),
+            refresh_reward_enabled: None,
+            mint_to_frozen_enabled: None,
+        };
+        let _info = mock_info("creator", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        assert_eq!(0, _res.messages.len());
+
+        let _msg = ExecuteMsg::SetMinter {
+            minter: "new_fund".to_string(),
+            quota: amount,
         };
         let _info = mock_info("creator", &[]);
         let _res = execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
@@ -285,11 +596,2340 @@ mod tests {
         assert_eq!(get_balance(deps.as_ref(), "lucky"), Uint128::new(112233));
 
         // Negative test case with insufficient permissions, only seilor_fund
-        let _msg = ExecuteMsg::Burn { amount };
+        let _msg = ExecuteMsg::Burn {
+            amount,
+            reason: None,
+        };
         let _info = mock_info("lucky", &[]);
         let _res = execute(deps.as_mut(), mock_env(), _info, _msg);
         assert!(_res.is_ok());
 
         assert_eq!(get_balance(deps.as_ref(), "lucky"), Uint128::zero());
     }
+
+    #[test]
+    fn test_freeze() {
+        let mut deps = mock_dependencies_with_balance(&[]);
+        let max_supply = 1000000u128;
+        let amount = Uint128::from(1000u128);
+
+        let instantiate_msg = default_instantiate(max_supply);
+        let _info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), mock_env(), _info, instantiate_msg).unwrap();
+
+        let _msg = ExecuteMsg::SetMinter {
+            minter: "minter".to_string(),
+            quota: Uint128::from(max_supply),
+        };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::Mint {
+            recipient: "lucky".to_string(),
+            amount,
+            contract: None,
+            msg: None,
+        };
+        let _info = mock_info("minter", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+
+        // Negative test case with insufficient permissions, only gov
+        let _msg = ExecuteMsg::Freeze {
+            account: "lucky".to_string(),
+        };
+        let _info = mock_info("lucky", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg.clone());
+        match _res {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        assert_eq!(
+            query_frozen_accounts(deps.as_ref(), None, None)
+                .unwrap()
+                .accounts,
+            vec![Addr::unchecked("lucky")]
+        );
+
+        // frozen accounts can neither transfer nor burn
+        let _msg = ExecuteMsg::Transfer {
+            recipient: "other".to_string(),
+            amount,
+        };
+        let _info = mock_info("lucky", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg);
+        match _res {
+            Err(ContractError::AccountFrozen(account)) => assert_eq!(account, "lucky"),
+            _ => panic!("Must return account frozen error"),
+        }
+
+        let _msg = ExecuteMsg::Burn {
+            amount,
+            reason: None,
+        };
+        let _info = mock_info("lucky", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg);
+        assert!(matches!(_res, Err(ContractError::AccountFrozen(_))));
+
+        // nor be drained through an allowance
+        let _msg = ExecuteMsg::IncreaseAllowance {
+            spender: "spender".to_string(),
+            amount,
+            expires: None,
+        };
+        let _info = mock_info("lucky", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::TransferFrom {
+            owner: "lucky".to_string(),
+            recipient: "spender".to_string(),
+            amount,
+        };
+        let _info = mock_info("spender", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg);
+        assert!(matches!(_res, Err(ContractError::AccountFrozen(_))));
+
+        // minting to a frozen account is rejected unless enabled by gov
+        let _msg = ExecuteMsg::Mint {
+            recipient: "lucky".to_string(),
+            amount,
+            contract: None,
+            msg: None,
+        };
+        let _info = mock_info("minter", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info.clone(), _msg.clone());
+        assert!(matches!(_res, Err(ContractError::AccountFrozen(_))));
+
+        let _update_msg = ExecuteMsg::UpdateConfig {
+            fund: None,
+            gov: None,
+            refresh_reward_enabled: None,
+            mint_to_frozen_enabled: Some(true),
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            _update_msg,
+        )
+        .unwrap();
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "lucky"), Uint128::new(2000));
+
+        // the gov setting survives a code upgrade
+        let _msg = MigrateMsg {
+            fund_quota: None,
+            distribute_quota: None,
+        };
+        migrate(deps.as_mut(), mock_env(), _msg).unwrap();
+        assert!(
+            query_seilor_config(deps.as_ref())
+                .unwrap()
+                .mint_to_frozen_enabled
+        );
+
+        let _msg = ExecuteMsg::Unfreeze {
+            account: "lucky".to_string(),
+        };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), mock_env(), _info.clone(), _msg.clone()).unwrap();
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg);
+        match _res.unwrap_err() {
+            ContractError::Std(GenericErr { msg, .. }) => {
+                assert_eq!(msg, "Account not frozen")
+            }
+            _ => panic!("Must return generic error"),
+        }
+
+        let _msg = ExecuteMsg::Transfer {
+            recipient: "other".to_string(),
+            amount,
+        };
+        let _info = mock_info("lucky", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "other"), amount);
+    }
+
+    #[test]
+    fn test_transfer_tax() {
+        let mut deps = mock_dependencies_with_balance(&[]);
+        let max_supply = 1000000u128;
+        let amount = Uint128::from(10000u128);
+
+        let instantiate_msg = default_instantiate(max_supply);
+        let _info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), mock_env(), _info, instantiate_msg).unwrap();
+
+        let _msg = ExecuteMsg::UpdateConfig {
+            fund: Some(Addr::unchecked("fund")),
+            gov: None,
+            refresh_reward_enabled: None,
+            mint_to_frozen_enabled: None,
+        };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::SetMinter {
+            minter: "minter".to_string(),
+            quota: Uint128::from(max_supply),
+        };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::Mint {
+            recipient: "lucky".to_string(),
+            amount: Uint128::from(100000u128),
+            contract: None,
+            msg: None,
+        };
+        let _info = mock_info("minter", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+
+        // Negative test case with insufficient permissions, only gov
+        let _msg = ExecuteMsg::UpdateTransferTax {
+            tax_bps: Some(100),
+            burn_bps: Some(4000),
+            receiver: None,
+        };
+        let _info = mock_info("lucky", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg.clone());
+        match _res {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        let tax_config = query_transfer_tax(deps.as_ref()).unwrap();
+        assert_eq!(tax_config.tax_bps, 100);
+        assert_eq!(tax_config.burn_bps, 4000);
+        assert_eq!(tax_config.receiver, Addr::unchecked("fund"));
+
+        // 1% tax, 40% of it burned and the rest sent to the fund
+        let simulation =
+            query_simulate_transfer(deps.as_ref(), "lucky".to_string(), "other".to_string(), amount)
+                .unwrap();
+        assert_eq!(simulation.tax_amount, Uint128::new(100));
+        assert_eq!(simulation.burn_amount, Uint128::new(40));
+        assert_eq!(simulation.receiver_amount, Uint128::new(60));
+        assert_eq!(simulation.net_amount, Uint128::new(9900));
+
+        let _msg = ExecuteMsg::Transfer {
+            recipient: "other".to_string(),
+            amount,
+        };
+        let _info = mock_info("lucky", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        assert!(_res
+            .attributes
+            .iter()
+            .any(|a| a.key == "tax_amount" && a.value == "100"));
+        assert_eq!(get_balance(deps.as_ref(), "lucky"), Uint128::new(90000));
+        assert_eq!(get_balance(deps.as_ref(), "other"), Uint128::new(9900));
+        assert_eq!(get_balance(deps.as_ref(), "fund"), Uint128::new(60));
+        assert_eq!(
+            query_token_info(deps.as_ref()).unwrap().total_supply,
+            Uint128::new(99960)
+        );
+
+        // the spender's allowance covers the gross amount
+        let _msg = ExecuteMsg::IncreaseAllowance {
+            spender: "spender".to_string(),
+            amount: Uint128::from(15000u128),
+            expires: None,
+        };
+        let _info = mock_info("lucky", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::TransferFrom {
+            owner: "lucky".to_string(),
+            recipient: "other".to_string(),
+            amount,
+        };
+        let _info = mock_info("spender", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "lucky"), Uint128::new(80000));
+        assert_eq!(get_balance(deps.as_ref(), "other"), Uint128::new(19800));
+        assert_eq!(
+            query_allowance(deps.as_ref(), "lucky".to_string(), "spender".to_string())
+                .unwrap()
+                .allowance,
+            Uint128::new(5000)
+        );
+
+        // exempt accounts pay no tax
+        let _msg = ExecuteMsg::SetTaxExempt {
+            account: "other".to_string(),
+            exempt: true,
+        };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::Transfer {
+            recipient: "other".to_string(),
+            amount,
+        };
+        let _info = mock_info("lucky", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        assert!(!_res.attributes.iter().any(|a| a.key == "tax_amount"));
+        assert_eq!(get_balance(deps.as_ref(), "other"), Uint128::new(29800));
+
+        let _msg = ExecuteMsg::UpdateTransferTax {
+            tax_bps: Some(10000),
+            burn_bps: None,
+            receiver: None,
+        };
+        let _info = mock_info("creator", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg);
+        match _res.unwrap_err() {
+            ContractError::Std(GenericErr { msg, .. }) => assert_eq!(msg, "Invalid tax_bps"),
+            _ => panic!("Must return generic error"),
+        }
+    }
+
+    #[test]
+    fn test_permit() {
+        let mut deps = mock_dependencies_with_balance(&[]);
+        let max_supply = 1000000u128;
+        let amount = Uint128::from(5000u128);
+
+        let instantiate_msg = default_instantiate(max_supply);
+        let _info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), mock_env(), _info, instantiate_msg).unwrap();
+
+        let signing_key = SigningKey::from_slice(&[7u8; 32]).unwrap();
+        let pubkey = signing_key
+            .verifying_key()
+            .to_encoded_point(true)
+            .as_bytes()
+            .to_vec();
+        let owner = pubkey_to_address(&pubkey, "sei").unwrap();
+        let env = mock_env();
+
+        let sign = |spender: &str, nonce: u64| -> Binary {
+            let permit_msg = PermitMsg {
+                chain_id: env.block.chain_id.clone(),
+                contract: env.contract.address.to_string(),
+                owner: owner.clone(),
+                spender: spender.to_string(),
+                amount,
+                expires: None,
+                nonce,
+            };
+            let signature: Signature = signing_key.sign(&to_vec(&permit_msg).unwrap());
+            Binary::from(signature.to_bytes().to_vec())
+        };
+        let permit = |spender: &str, nonce: u64, signature: Binary| ExecuteMsg::Permit {
+            owner: owner.clone(),
+            spender: spender.to_string(),
+            amount,
+            expires: None,
+            nonce,
+            signature,
+            pubkey: Binary::from(pubkey.clone()),
+        };
+
+        // anyone can relay the signed permit
+        let _msg = permit("treasure", 0, sign("treasure", 0));
+        let _info = mock_info("relayer", &[]);
+        execute(deps.as_mut(), env.clone(), _info, _msg.clone()).unwrap();
+        assert_eq!(
+            query_allowance(deps.as_ref(), owner.clone(), "treasure".to_string())
+                .unwrap()
+                .allowance,
+            amount
+        );
+        assert_eq!(
+            query_permit_nonce(deps.as_ref(), owner.clone())
+                .unwrap()
+                .nonce,
+            1
+        );
+
+        // replaying the same permit fails
+        let _info = mock_info("relayer", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), _info, _msg);
+        assert_eq!(_res.unwrap_err(), ContractError::InvalidPermitNonce(1));
+
+        // signature over a different spender
+        let _msg = permit("attacker", 1, sign("treasure", 1));
+        let _info = mock_info("relayer", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), _info, _msg);
+        assert_eq!(_res.unwrap_err(), ContractError::InvalidPermitSignature {});
+
+        // pubkey of another account
+        let _msg = ExecuteMsg::Permit {
+            owner: "sei1qyqszqgpqyqszqgpqyqszqgpqyqszqgpjnp7du".to_string(),
+            spender: "treasure".to_string(),
+            amount,
+            expires: None,
+            nonce: 0,
+            signature: sign("treasure", 0),
+            pubkey: Binary::from(pubkey.clone()),
+        };
+        let _info = mock_info("relayer", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), _info, _msg);
+        assert_eq!(_res.unwrap_err(), ContractError::InvalidPermitSignature {});
+
+        let _msg = permit("treasure", 1, sign("treasure", 1));
+        let _info = mock_info("relayer", &[]);
+        execute(deps.as_mut(), env.clone(), _info, _msg).unwrap();
+        assert_eq!(
+            query_allowance(deps.as_ref(), owner.clone(), "treasure".to_string())
+                .unwrap()
+                .allowance,
+            Uint128::new(10000)
+        );
+
+        // a permit can't set a cw20 allowance next to a periodic one
+        let _msg = ExecuteMsg::IncreasePeriodicAllowance {
+            spender: "fund".to_string(),
+            amount_per_period: Uint128::from(1000u128),
+            period_seconds: 86400,
+            expires: None,
+        };
+        let _info = mock_info(&owner, &[]);
+        execute(deps.as_mut(), env.clone(), _info, _msg).unwrap();
+        let _msg = permit("fund", 2, sign("fund", 2));
+        let _info = mock_info("relayer", &[]);
+        let _res = execute(deps.as_mut(), env, _info, _msg);
+        match _res.unwrap_err() {
+            ContractError::Std(GenericErr { msg, .. }) => {
+                assert_eq!(msg, "Spender already has a periodic allowance")
+            }
+            _ => panic!("Must return generic error"),
+        }
+        assert_eq!(
+            query_allowance(deps.as_ref(), owner, "fund".to_string())
+                .unwrap()
+                .allowance,
+            Uint128::zero()
+        );
+    }
+
+    #[test]
+    fn test_batch() {
+        let mut deps = mock_dependencies_with_balance(&[]);
+        let max_supply = 1000000u128;
+
+        let instantiate_msg = default_instantiate(max_supply);
+        let _info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), mock_env(), _info, instantiate_msg).unwrap();
+
+        let _msg = ExecuteMsg::SetMinter {
+            minter: "minter".to_string(),
+            quota: Uint128::from(10000u128),
+        };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+
+        // Negative test case with insufficient permissions, only registered minters
+        let _msg = ExecuteMsg::MintBatch {
+            mints: vec![("alice".to_string(), Uint128::from(1000u128))],
+        };
+        let _info = mock_info("lucky", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg);
+        match _res {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+
+        // the quota is checked across all legs
+        let _msg = ExecuteMsg::MintBatch {
+            mints: vec![
+                ("alice".to_string(), Uint128::from(6000u128)),
+                ("bob".to_string(), Uint128::from(6000u128)),
+            ],
+        };
+        let _info = mock_info("minter", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg);
+        match _res.unwrap_err() {
+            ContractError::Std(GenericErr { msg, .. }) => assert_eq!(msg, "Minter quota exceeded"),
+            _ => panic!("Must return generic error"),
+        }
+
+        // the mock storage keeps the writes of the failed legs, start over
+        let mut deps = mock_dependencies_with_balance(&[]);
+        let instantiate_msg = default_instantiate(max_supply);
+        let _info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), mock_env(), _info, instantiate_msg).unwrap();
+        let _msg = ExecuteMsg::SetMinter {
+            minter: "minter".to_string(),
+            quota: Uint128::from(10000u128),
+        };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::MintBatch {
+            mints: vec![
+                ("alice".to_string(), Uint128::from(6000u128)),
+                ("bob".to_string(), Uint128::from(4000u128)),
+            ],
+        };
+        let _info = mock_info("minter", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        assert_eq!(
+            _res.attributes
+                .iter()
+                .filter(|a| a.key == "action" && a.value == "mint")
+                .count(),
+            2
+        );
+        assert_eq!(get_balance(deps.as_ref(), "alice"), Uint128::new(6000));
+        assert_eq!(get_balance(deps.as_ref(), "bob"), Uint128::new(4000));
+
+        let _msg = ExecuteMsg::TransferBatch {
+            transfers: vec![
+                ("bob".to_string(), Uint128::from(1000u128)),
+                ("carol".to_string(), Uint128::from(2000u128)),
+            ],
+        };
+        let _info = mock_info("alice", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        assert_eq!(
+            _res.attributes
+                .iter()
+                .filter(|a| a.key == "action" && a.value == "transfer")
+                .count(),
+            2
+        );
+        assert_eq!(get_balance(deps.as_ref(), "alice"), Uint128::new(3000));
+        assert_eq!(get_balance(deps.as_ref(), "bob"), Uint128::new(5000));
+        assert_eq!(get_balance(deps.as_ref(), "carol"), Uint128::new(2000));
+
+        let _msg = ExecuteMsg::TransferBatch { transfers: vec![] };
+        let _info = mock_info("alice", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg);
+        match _res.unwrap_err() {
+            ContractError::Std(GenericErr { msg, .. }) => assert_eq!(msg, "Empty batch"),
+            _ => panic!("Must return generic error"),
+        }
+    }
+
+    #[test]
+    fn test_burn_stats() {
+        let mut deps = mock_dependencies_with_balance(&[]);
+        let max_supply = 1000000u128;
+
+        let instantiate_msg = default_instantiate(max_supply);
+        let _info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), mock_env(), _info, instantiate_msg).unwrap();
+
+        let _msg = ExecuteMsg::SetMinter {
+            minter: "minter".to_string(),
+            quota: Uint128::from(max_supply),
+        };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::Mint {
+            recipient: "lucky".to_string(),
+            amount: Uint128::from(10000u128),
+            contract: None,
+            msg: None,
+        };
+        let _info = mock_info("minter", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::Burn {
+            amount: Uint128::from(1000u128),
+            reason: None,
+        };
+        let _info = mock_info("lucky", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::IncreaseAllowance {
+            spender: "fund".to_string(),
+            amount: Uint128::from(2000u128),
+            expires: None,
+        };
+        let _info = mock_info("lucky", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::BurnFrom {
+            owner: "lucky".to_string(),
+            amount: Uint128::from(2000u128),
+            reason: Some("punish".to_string()),
+        };
+        let _info = mock_info("fund", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        assert!(_res
+            .attributes
+            .iter()
+            .any(|a| a.key == "reason" && a.value == "punish"));
+
+        let burn_stats = query_burn_stats(deps.as_ref(), Some("lucky".to_string())).unwrap();
+        assert_eq!(burn_stats.total_burned, Uint128::new(3000));
+        assert_eq!(burn_stats.burned, Some(Uint128::new(3000)));
+        let burn_stats = query_burn_stats(deps.as_ref(), None).unwrap();
+        assert_eq!(burn_stats.burned, None);
+
+        let burns = query_burn_history(deps.as_ref(), None, None)
+            .unwrap()
+            .burns;
+        assert_eq!(burns.len(), 2);
+        assert_eq!(burns[0].id, 1);
+        assert_eq!(burns[0].reason, None);
+        assert_eq!(burns[1].amount, Uint128::new(2000));
+        assert_eq!(burns[1].reason, Some("punish".to_string()));
+        assert_eq!(burns[1].height, mock_env().block.height);
+
+        let burns = query_burn_history(deps.as_ref(), Some(1), None)
+            .unwrap()
+            .burns;
+        assert_eq!(burns.len(), 1);
+        assert_eq!(burns[0].id, 2);
+
+        let _msg = ExecuteMsg::Burn {
+            amount: Uint128::from(1u128),
+            reason: Some("x".repeat(129)),
+        };
+        let _info = mock_info("lucky", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg);
+        match _res.unwrap_err() {
+            ContractError::Std(GenericErr { msg, .. }) => assert_eq!(msg, "Burn reason too long"),
+            _ => panic!("Must return generic error"),
+        }
+    }
+
+    #[test]
+    fn test_circulating_supply() {
+        let mut deps = mock_dependencies_with_balance(&[]);
+        let max_supply = 1000000u128;
+
+        let instantiate_msg = default_instantiate(max_supply);
+        let _info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), mock_env(), _info, instantiate_msg).unwrap();
+
+        let _msg = ExecuteMsg::SetMinter {
+            minter: "minter".to_string(),
+            quota: Uint128::from(max_supply),
+        };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::MintBatch {
+            mints: vec![
+                ("treasury".to_string(), Uint128::from(300000u128)),
+                ("lucky".to_string(), Uint128::from(100000u128)),
+            ],
+        };
+        let _info = mock_info("minter", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+
+        // Negative test case with insufficient permissions, only gov
+        let _msg = ExecuteMsg::SetSupplyExcluded {
+            account: "treasury".to_string(),
+            excluded: true,
+        };
+        let _info = mock_info("lucky", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg.clone());
+        match _res {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+
+        let supply = query_circulating_supply(deps.as_ref()).unwrap();
+        assert_eq!(supply.total_supply, Uint128::new(400000));
+        assert_eq!(supply.excluded_supply, Uint128::new(300000));
+        assert_eq!(supply.circulating_supply, Uint128::new(100000));
+        assert_eq!(supply.max_supply, Uint128::new(max_supply));
+        assert_eq!(supply.mintable, Uint128::new(600000));
+
+        let _msg = ExecuteMsg::SetSupplyExcluded {
+            account: "treasury".to_string(),
+            excluded: false,
+        };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        let supply = query_circulating_supply(deps.as_ref()).unwrap();
+        assert_eq!(supply.circulating_supply, Uint128::new(400000));
+
+        // the exclusion list is capped
+        for i in 0..30 {
+            let _msg = ExecuteMsg::SetSupplyExcluded {
+                account: format!("excluded{}", i),
+                excluded: true,
+            };
+            let _info = mock_info("creator", &[]);
+            execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        }
+        let _msg = ExecuteMsg::SetSupplyExcluded {
+            account: "excluded0".to_string(),
+            excluded: true,
+        };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::SetSupplyExcluded {
+            account: "treasury".to_string(),
+            excluded: true,
+        };
+        let _info = mock_info("creator", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg);
+        match _res.unwrap_err() {
+            ContractError::Std(GenericErr { msg, .. }) => {
+                assert_eq!(msg, "Too many supply excluded accounts")
+            }
+            _ => panic!("Must return generic error"),
+        }
+    }
+
+    #[test]
+    fn test_top_holders() {
+        let mut deps = mock_dependencies_with_balance(&[]);
+        let max_supply = 1000000u128;
+
+        let instantiate_msg = default_instantiate(max_supply);
+        let _info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), mock_env(), _info, instantiate_msg).unwrap();
+
+        let _msg = ExecuteMsg::SetMinter {
+            minter: "minter".to_string(),
+            quota: Uint128::from(max_supply),
+        };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::MintBatch {
+            mints: vec![
+                ("alice".to_string(), Uint128::from(100u128)),
+                ("bob".to_string(), Uint128::from(300u128)),
+                ("carol".to_string(), Uint128::from(200u128)),
+            ],
+        };
+        let _info = mock_info("minter", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+
+        let top_holders = |deps: Deps, start_after: Option<&str>, limit: Option<u32>| {
+            query_top_holders(deps, start_after.map(|s| s.to_string()), limit)
+                .unwrap()
+                .holders
+                .into_iter()
+                .map(|holder| (holder.address.to_string(), holder.balance.u128()))
+                .collect::<Vec<(String, u128)>>()
+        };
+        assert_eq!(
+            top_holders(deps.as_ref(), None, None),
+            vec![
+                ("bob".to_string(), 300),
+                ("carol".to_string(), 200),
+                ("alice".to_string(), 100)
+            ]
+        );
+
+        // the index follows transfers and drops emptied accounts
+        let _msg = ExecuteMsg::Transfer {
+            recipient: "alice".to_string(),
+            amount: Uint128::from(300u128),
+        };
+        let _info = mock_info("bob", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        assert_eq!(
+            top_holders(deps.as_ref(), None, Some(1)),
+            vec![("alice".to_string(), 400)]
+        );
+        assert_eq!(
+            top_holders(deps.as_ref(), Some("alice"), None),
+            vec![("carol".to_string(), 200)]
+        );
+    }
+
+    #[test]
+    fn test_escrow() {
+        let mut deps = mock_dependencies_with_balance(&[]);
+        let max_supply = 1000000u128;
+        let env = mock_env();
+        let now = env.block.time.seconds();
+
+        let instantiate_msg = default_instantiate(max_supply);
+        let _info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), env.clone(), _info, instantiate_msg).unwrap();
+
+        let _msg = ExecuteMsg::SetMinter {
+            minter: "minter".to_string(),
+            quota: Uint128::from(max_supply),
+        };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), env.clone(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::Mint {
+            recipient: "lucky".to_string(),
+            amount: Uint128::from(10000u128),
+            contract: None,
+            msg: None,
+        };
+        let _info = mock_info("minter", &[]);
+        execute(deps.as_mut(), env.clone(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::TransferLocked {
+            recipient: "partner".to_string(),
+            amount: Uint128::from(3000u128),
+            release_at: now,
+            cancelable: false,
+        };
+        let _info = mock_info("lucky", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), _info, _msg);
+        match _res.unwrap_err() {
+            ContractError::Std(GenericErr { msg, .. }) => assert_eq!(msg, "Invalid release_at"),
+            _ => panic!("Must return generic error"),
+        }
+
+        for (amount, cancelable) in [(3000u128, false), (2000u128, true)] {
+            let _msg = ExecuteMsg::TransferLocked {
+                recipient: "partner".to_string(),
+                amount: Uint128::from(amount),
+                release_at: now + 100,
+                cancelable,
+            };
+            let _info = mock_info("lucky", &[]);
+            execute(deps.as_mut(), env.clone(), _info, _msg).unwrap();
+        }
+        assert_eq!(get_balance(deps.as_ref(), "lucky"), Uint128::new(5000));
+        assert_eq!(
+            get_balance(deps.as_ref(), env.contract.address.as_str()),
+            Uint128::new(5000)
+        );
+        let escrows = query_escrows_by_recipient(deps.as_ref(), "partner".to_string(), None, None)
+            .unwrap()
+            .escrows;
+        assert_eq!(escrows.len(), 2);
+        assert_eq!(escrows[0].id, 1);
+        assert_eq!(escrows[0].amount, Uint128::new(3000));
+        assert_eq!(
+            query_escrows_by_sender(deps.as_ref(), "lucky".to_string(), Some(1), None)
+                .unwrap()
+                .escrows[0]
+                .id,
+            2
+        );
+
+        // nothing can be claimed before release
+        let _msg = ExecuteMsg::ClaimLocked { id: 1 };
+        let _info = mock_info("partner", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), _info, _msg);
+        match _res.unwrap_err() {
+            ContractError::Std(GenericErr { msg, .. }) => assert_eq!(msg, "Escrow not released"),
+            _ => panic!("Must return generic error"),
+        }
+
+        let _msg = ExecuteMsg::CancelLocked { id: 1 };
+        let _info = mock_info("lucky", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), _info, _msg);
+        match _res.unwrap_err() {
+            ContractError::Std(GenericErr { msg, .. }) => {
+                assert_eq!(msg, "Escrow not cancelable")
+            }
+            _ => panic!("Must return generic error"),
+        }
+
+        let _msg = ExecuteMsg::CancelLocked { id: 2 };
+        let _info = mock_info("lucky", &[]);
+        execute(deps.as_mut(), env.clone(), _info, _msg).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "lucky"), Uint128::new(7000));
+        assert!(query_escrow(deps.as_ref(), 2).is_err());
+
+        let mut released_env = env.clone();
+        released_env.block.time = released_env.block.time.plus_seconds(100);
+
+        // Negative test case with insufficient permissions, only the recipient
+        let _msg = ExecuteMsg::ClaimLocked { id: 1 };
+        let _info = mock_info("lucky", &[]);
+        let _res = execute(deps.as_mut(), released_env.clone(), _info, _msg.clone());
+        match _res {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+
+        let _info = mock_info("partner", &[]);
+        execute(deps.as_mut(), released_env, _info, _msg).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "partner"), Uint128::new(3000));
+        assert_eq!(
+            get_balance(deps.as_ref(), env.contract.address.as_str()),
+            Uint128::zero()
+        );
+        assert!(
+            query_escrows_by_recipient(deps.as_ref(), "partner".to_string(), None, None)
+                .unwrap()
+                .escrows
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_stream() {
+        let mut deps = mock_dependencies_with_balance(&[]);
+        let max_supply = 1000000u128;
+        let env = mock_env();
+        let now = env.block.time.seconds();
+        let at = |seconds: u64| {
+            let mut env = mock_env();
+            env.block.time = env.block.time.plus_seconds(seconds);
+            env
+        };
+
+        let instantiate_msg = default_instantiate(max_supply);
+        let _info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), env.clone(), _info, instantiate_msg).unwrap();
+
+        let _msg = ExecuteMsg::SetMinter {
+            minter: "minter".to_string(),
+            quota: Uint128::from(max_supply),
+        };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), env.clone(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::Mint {
+            recipient: "dao".to_string(),
+            amount: Uint128::from(20000u128),
+            contract: None,
+            msg: None,
+        };
+        let _info = mock_info("minter", &[]);
+        execute(deps.as_mut(), env.clone(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::CreateStream {
+            recipient: "contributor".to_string(),
+            amount: Uint128::from(10000u128),
+            start: now + 100,
+            end: now + 100,
+        };
+        let _info = mock_info("dao", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), _info, _msg);
+        match _res.unwrap_err() {
+            ContractError::Std(GenericErr { msg, .. }) => assert_eq!(msg, "Invalid stream period"),
+            _ => panic!("Must return generic error"),
+        }
+
+        for _ in 0..2 {
+            let _msg = ExecuteMsg::CreateStream {
+                recipient: "contributor".to_string(),
+                amount: Uint128::from(10000u128),
+                start: now,
+                end: now + 1000,
+            };
+            let _info = mock_info("dao", &[]);
+            execute(deps.as_mut(), env.clone(), _info, _msg).unwrap();
+        }
+        assert_eq!(get_balance(deps.as_ref(), "dao"), Uint128::zero());
+
+        // a quarter of the period has passed
+        let stream = query_stream(deps.as_ref(), at(250), 1).unwrap();
+        assert_eq!(stream.withdrawable, Uint128::new(2500));
+
+        // Negative test case with insufficient permissions, only the recipient
+        let _msg = ExecuteMsg::WithdrawStream { id: 1 };
+        let _info = mock_info("dao", &[]);
+        let _res = execute(deps.as_mut(), at(250), _info, _msg.clone());
+        match _res {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+
+        let _info = mock_info("contributor", &[]);
+        execute(deps.as_mut(), at(250), _info, _msg.clone()).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "contributor"), Uint128::new(2500));
+
+        let _info = mock_info("contributor", &[]);
+        let _res = execute(deps.as_mut(), at(250), _info, _msg);
+        match _res.unwrap_err() {
+            ContractError::Std(GenericErr { msg, .. }) => assert_eq!(msg, "Nothing to withdraw"),
+            _ => panic!("Must return generic error"),
+        }
+
+        // cancelling at 60% splits the rest pro rata
+        let _msg = ExecuteMsg::CancelStream { id: 1 };
+        let _info = mock_info("dao", &[]);
+        execute(deps.as_mut(), at(600), _info, _msg).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "contributor"), Uint128::new(6000));
+        assert_eq!(get_balance(deps.as_ref(), "dao"), Uint128::new(4000));
+        assert!(query_stream(deps.as_ref(), at(600), 1).is_err());
+
+        // the second stream is closed once fully withdrawn
+        let _msg = ExecuteMsg::WithdrawStream { id: 2 };
+        let _info = mock_info("contributor", &[]);
+        execute(deps.as_mut(), at(2000), _info, _msg).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "contributor"), Uint128::new(16000));
+        assert!(
+            query_streams_by_recipient(deps.as_ref(), at(2000), "contributor".to_string(), None, None)
+                .unwrap()
+                .streams
+                .is_empty()
+        );
+        assert_eq!(
+            get_balance(deps.as_ref(), env.contract.address.as_str()),
+            Uint128::zero()
+        );
+    }
+
+    #[test]
+    fn test_recover_tokens() {
+        let mut deps = mock_dependencies_with_balance(&coins(100, "usei"));
+        let max_supply = 1000000u128;
+        let env = mock_env();
+
+        let instantiate_msg = default_instantiate(max_supply);
+        let _info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), env.clone(), _info, instantiate_msg).unwrap();
+
+        // Negative test case with insufficient permissions, only gov
+        let _msg = ExecuteMsg::RecoverTokens {
+            asset: RecoverAsset::Native {
+                denom: "usei".to_string(),
+            },
+            recipient: "lucky".to_string(),
+            amount: Uint128::from(100u128),
+        };
+        let _info = mock_info("lucky", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), _info, _msg.clone());
+        match _res {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+
+        let _info = mock_info("creator", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), _info, _msg).unwrap();
+        assert_eq!(
+            _res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "lucky".to_string(),
+                amount: coins(100, "usei"),
+            })
+        );
+
+        let _msg = ExecuteMsg::RecoverTokens {
+            asset: RecoverAsset::Cw20 {
+                contract_addr: "other_token".to_string(),
+            },
+            recipient: "lucky".to_string(),
+            amount: Uint128::from(50u128),
+        };
+        let _info = mock_info("creator", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), _info, _msg).unwrap();
+        match &_res.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) => {
+                assert_eq!(contract_addr, "other_token");
+                assert_eq!(
+                    from_binary::<Cw20ExecuteMsg>(msg).unwrap(),
+                    Cw20ExecuteMsg::Transfer {
+                        recipient: "lucky".to_string(),
+                        amount: Uint128::from(50u128),
+                    }
+                );
+            }
+            _ => panic!("Must send a cw20 transfer"),
+        }
+
+        // SEILOR held by the contract belongs to escrows and streams
+        let _msg = ExecuteMsg::RecoverTokens {
+            asset: RecoverAsset::Cw20 {
+                contract_addr: env.contract.address.to_string(),
+            },
+            recipient: "lucky".to_string(),
+            amount: Uint128::from(50u128),
+        };
+        let _info = mock_info("creator", &[]);
+        let _res = execute(deps.as_mut(), env, _info, _msg);
+        match _res.unwrap_err() {
+            ContractError::Std(GenericErr { msg, .. }) => assert_eq!(msg, "Cannot recover SEILOR"),
+            _ => panic!("Must return generic error"),
+        }
+    }
+
+    #[test]
+    fn test_mint_history() {
+        let mut deps = mock_dependencies_with_balance(&[]);
+        let max_supply = 1000000u128;
+
+        let instantiate_msg = default_instantiate(max_supply);
+        let _info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), mock_env(), _info, instantiate_msg).unwrap();
+
+        for minter in ["fund", "distribute"] {
+            let _msg = ExecuteMsg::SetMinter {
+                minter: minter.to_string(),
+                quota: Uint128::from(max_supply),
+            };
+            let _info = mock_info("creator", &[]);
+            execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        }
+
+        let _msg = ExecuteMsg::Mint {
+            recipient: "alice".to_string(),
+            amount: Uint128::from(100u128),
+            contract: Some("treasure".to_string()),
+            msg: Some(Binary::from(b"{}".to_vec())),
+        };
+        let _info = mock_info("fund", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        assert!(_res
+            .attributes
+            .iter()
+            .any(|a| a.key == "mint_id" && a.value == "1"));
+
+        let _msg = ExecuteMsg::MintBatch {
+            mints: vec![
+                ("bob".to_string(), Uint128::from(200u128)),
+                ("carol".to_string(), Uint128::from(300u128)),
+            ],
+        };
+        let _info = mock_info("distribute", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+
+        let mints = query_mint_history(deps.as_ref(), None, None, None)
+            .unwrap()
+            .mints;
+        assert_eq!(mints.len(), 3);
+        assert_eq!(mints[0].minter, Addr::unchecked("fund"));
+        assert_eq!(mints[0].recipient, Addr::unchecked("alice"));
+        assert_eq!(mints[0].contract, Some(Addr::unchecked("treasure")));
+        assert_eq!(mints[0].height, mock_env().block.height);
+        assert_eq!(mints[2].amount, Uint128::new(300));
+        assert_eq!(mints[2].contract, None);
+
+        // only the mints of `distribute`, after id 2
+        let mints = query_mint_history(
+            deps.as_ref(),
+            Some("distribute".to_string()),
+            Some(2),
+            None,
+        )
+        .unwrap()
+        .mints;
+        assert_eq!(mints.len(), 1);
+        assert_eq!(mints[0].id, 3);
+        assert_eq!(mints[0].recipient, Addr::unchecked("carol"));
+    }
+
+    #[test]
+    fn test_lower_max_supply() {
+        let mut deps = mock_dependencies_with_balance(&[]);
+        let max_supply = 1000000u128;
+
+        let instantiate_msg = default_instantiate(max_supply);
+        let _info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), mock_env(), _info, instantiate_msg).unwrap();
+
+        let _msg = ExecuteMsg::SetMinter {
+            minter: "minter".to_string(),
+            quota: Uint128::from(max_supply),
+        };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::Mint {
+            recipient: "alice".to_string(),
+            amount: Uint128::from(300000u128),
+            contract: None,
+            msg: None,
+        };
+        let _info = mock_info("minter", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+
+        // Negative test case with insufficient permissions
+        let _msg = ExecuteMsg::LowerMaxSupply { max_supply: 500000 };
+        let _info = mock_info("minter", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg);
+        match _res {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+
+        // the cap can't be raised
+        let _msg = ExecuteMsg::LowerMaxSupply {
+            max_supply: max_supply + 1,
+        };
+        let _info = mock_info("creator", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg);
+        match _res.unwrap_err() {
+            ContractError::Std(GenericErr { msg, .. }) => {
+                assert_eq!(msg, "max_supply can only be lowered")
+            }
+            _ => panic!("Must return generic error"),
+        }
+
+        // nor lowered below the current supply
+        let _msg = ExecuteMsg::LowerMaxSupply { max_supply: 299999 };
+        let _info = mock_info("creator", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg);
+        match _res.unwrap_err() {
+            ContractError::Std(GenericErr { msg, .. }) => {
+                assert_eq!(msg, "max_supply below current supply")
+            }
+            _ => panic!("Must return generic error"),
+        }
+
+        let _msg = ExecuteMsg::LowerMaxSupply { max_supply: 500000 };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+
+        let config = query_seilor_config(deps.as_ref()).unwrap();
+        assert_eq!(config.max_supply, 500000);
+        assert_eq!(config.total_supply, Uint128::from(300000u128));
+        assert_eq!(config.remaining, Uint128::from(200000u128));
+        let token_info = TOKEN_INFO.load(deps.as_ref().storage).unwrap();
+        assert_eq!(
+            token_info.mint.unwrap().cap,
+            Some(Uint128::from(500000u128))
+        );
+
+        // the cw20 cap is enforced on the next mint
+        let _msg = ExecuteMsg::Mint {
+            recipient: "alice".to_string(),
+            amount: Uint128::from(200001u128),
+            contract: None,
+            msg: None,
+        };
+        let _info = mock_info("minter", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg);
+        assert!(_res.is_err());
+    }
+
+    #[test]
+    fn test_wrap_denom() {
+        let mut deps = mock_dependencies_with_balance(&[]);
+        let max_supply = 1000000u128;
+
+        let instantiate_msg = default_instantiate(max_supply);
+        let _info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), mock_env(), _info, instantiate_msg).unwrap();
+
+        // Negative test case with insufficient permissions
+        let _msg = ExecuteMsg::CreateWrapDenom {
+            subdenom: "seilor".to_string(),
+        };
+        let _info = mock_info("lucky", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg);
+        match _res {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+
+        let _msg = ExecuteMsg::CreateWrapDenom {
+            subdenom: "seilor".to_string(),
+        };
+        let _info = mock_info("creator", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        // MsgCreateDenom { sender: "cosmos2contract", subdenom: "seilor" }
+        assert_eq!(
+            _res.messages[0].msg,
+            CosmosMsg::Stargate {
+                type_url: "/seiprotocol.seichain.tokenfactory.MsgCreateDenom".to_string(),
+                value: Binary::from(b"\x0a\x0fcosmos2contract\x12\x06seilor".to_vec()),
+            }
+        );
+        let denom = format!("factory/{}/seilor", mock_env().contract.address);
+
+        let _msg = ExecuteMsg::CreateWrapDenom {
+            subdenom: "other".to_string(),
+        };
+        let _info = mock_info("creator", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg);
+        match _res.unwrap_err() {
+            ContractError::Std(GenericErr { msg, .. }) => {
+                assert_eq!(msg, "Wrap denom already created")
+            }
+            _ => panic!("Must return generic error"),
+        }
+
+        let _msg = ExecuteMsg::SetMinter {
+            minter: "minter".to_string(),
+            quota: Uint128::from(max_supply),
+        };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        let _msg = ExecuteMsg::Mint {
+            recipient: "alice".to_string(),
+            amount: Uint128::from(1000u128),
+            contract: None,
+            msg: None,
+        };
+        let _info = mock_info("minter", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+
+        // wrap locks the cw20 and mints the denom to the contract, then sends it on
+        let _msg = ExecuteMsg::Wrap {
+            amount: Uint128::from(600u128),
+        };
+        let _info = mock_info("alice", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "alice"), Uint128::new(400));
+        assert_eq!(
+            get_balance(deps.as_ref(), mock_env().contract.address),
+            Uint128::new(600)
+        );
+        let mint_msg = match &_res.messages[0].msg {
+            CosmosMsg::Stargate { type_url, value } => {
+                assert_eq!(type_url, "/seiprotocol.seichain.tokenfactory.MsgMint");
+                value.clone()
+            }
+            _ => panic!("Must return stargate message"),
+        };
+        // MsgMint { sender, amount: Coin { denom, amount: "600" } }
+        let mut coin = vec![0x0a, denom.len() as u8];
+        coin.extend_from_slice(denom.as_bytes());
+        coin.extend_from_slice(b"\x12\x03600");
+        let mut value = b"\x0a\x0fcosmos2contract\x12".to_vec();
+        value.push(coin.len() as u8);
+        value.extend(coin);
+        assert_eq!(mint_msg, Binary::from(value));
+        assert_eq!(
+            _res.messages[1].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "alice".to_string(),
+                amount: coins(600, &denom),
+            })
+        );
+        assert_eq!(
+            query_wrapped_supply(deps.as_ref()).unwrap().wrapped_supply,
+            Uint128::new(600)
+        );
+
+        // unwrap only takes the wrap denom
+        let _msg = ExecuteMsg::Unwrap {};
+        let _info = mock_info("alice", &coins(100, "usei"));
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg);
+        assert!(_res.is_err());
+
+        // anyone holding the denom can unwrap it, the denom sent along is burned
+        let _msg = ExecuteMsg::Unwrap {};
+        let _info = mock_info("bob", &coins(250, &denom));
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "bob"), Uint128::new(250));
+        assert_eq!(
+            query_wrapped_supply(deps.as_ref()).unwrap().wrapped_supply,
+            Uint128::new(350)
+        );
+        match &_res.messages[0].msg {
+            CosmosMsg::Stargate { type_url, .. } => {
+                assert_eq!(type_url, "/seiprotocol.seichain.tokenfactory.MsgBurn")
+            }
+            _ => panic!("Must return stargate message"),
+        }
+
+        // unwrapping is taxed like a transfer to the sender, out of the released amount
+        let _msg = ExecuteMsg::UpdateTransferTax {
+            tax_bps: Some(100),
+            burn_bps: Some(10000),
+            receiver: None,
+        };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        let _msg = ExecuteMsg::Unwrap {};
+        let _info = mock_info("carol", &coins(100, &denom));
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "carol"), Uint128::new(99));
+        assert_eq!(
+            get_balance(deps.as_ref(), mock_env().contract.address),
+            Uint128::new(250)
+        );
+        assert_eq!(
+            query_wrapped_supply(deps.as_ref()).unwrap().wrapped_supply,
+            Uint128::new(250)
+        );
+
+        // and held to the sender's launch limits
+        let _msg = ExecuteMsg::SetLaunchLimits {
+            max_transfer_amount: Some(Uint128::from(50u128)),
+            max_wallet_balance: None,
+            transfer_cooldown_seconds: 0,
+        };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), mock_env(), _info, _msg).unwrap();
+        let _msg = ExecuteMsg::Unwrap {};
+        let _info = mock_info("dave", &coins(100, &denom));
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg);
+        match _res.unwrap_err() {
+            ContractError::MaxTransferExceeded(max) => assert_eq!(max, Uint128::from(50u128)),
+            _ => panic!("Must return max transfer error"),
+        }
+
+        // the wrap denom isn't recoverable either
+        let _msg = ExecuteMsg::RecoverTokens {
+            asset: RecoverAsset::Native { denom },
+            recipient: "alice".to_string(),
+            amount: Uint128::from(100u128),
+        };
+        let _info = mock_info("creator", &[]);
+        let _res = execute(deps.as_mut(), mock_env(), _info, _msg);
+        match _res.unwrap_err() {
+            ContractError::Std(GenericErr { msg, .. }) => assert_eq!(msg, "Cannot recover SEILOR"),
+            _ => panic!("Must return generic error"),
+        }
+    }
+
+    #[test]
+    fn test_periodic_allowance() {
+        let mut deps = mock_dependencies_with_balance(&[]);
+        let max_supply = 1000000u128;
+        let env = mock_env();
+        let period = 30 * 86400u64;
+        let at = |seconds: u64| {
+            let mut env = mock_env();
+            env.block.time = env.block.time.plus_seconds(seconds);
+            env
+        };
+
+        let instantiate_msg = default_instantiate(max_supply);
+        let _info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), env.clone(), _info, instantiate_msg).unwrap();
+
+        let _msg = ExecuteMsg::SetMinter {
+            minter: "minter".to_string(),
+            quota: Uint128::from(max_supply),
+        };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), env.clone(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::Mint {
+            recipient: "alice".to_string(),
+            amount: Uint128::from(10000u128),
+            contract: None,
+            msg: None,
+        };
+        let _info = mock_info("minter", &[]);
+        execute(deps.as_mut(), env.clone(), _info, _msg).unwrap();
+
+        // a spender with a cw20 allowance gets no periodic one
+        let _msg = ExecuteMsg::IncreaseAllowance {
+            spender: "fund".to_string(),
+            amount: Uint128::from(50u128),
+            expires: None,
+        };
+        let _info = mock_info("alice", &[]);
+        execute(deps.as_mut(), env.clone(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::IncreasePeriodicAllowance {
+            spender: "fund".to_string(),
+            amount_per_period: Uint128::from(1000u128),
+            period_seconds: period,
+            expires: None,
+        };
+        let _info = mock_info("alice", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), _info, _msg);
+        match _res.unwrap_err() {
+            ContractError::Std(GenericErr { msg, .. }) => {
+                assert_eq!(msg, "Spender already has a cw20 allowance")
+            }
+            _ => panic!("Must return generic error"),
+        }
+
+        let _msg = ExecuteMsg::DecreaseAllowance {
+            spender: "fund".to_string(),
+            amount: Uint128::from(50u128),
+            expires: None,
+        };
+        let _info = mock_info("alice", &[]);
+        execute(deps.as_mut(), env.clone(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::IncreasePeriodicAllowance {
+            spender: "fund".to_string(),
+            amount_per_period: Uint128::from(1000u128),
+            period_seconds: 0,
+            expires: None,
+        };
+        let _info = mock_info("alice", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), _info, _msg);
+        match _res.unwrap_err() {
+            ContractError::Std(GenericErr { msg, .. }) => assert_eq!(msg, "Invalid period_seconds"),
+            _ => panic!("Must return generic error"),
+        }
+
+        let _msg = ExecuteMsg::IncreasePeriodicAllowance {
+            spender: "fund".to_string(),
+            amount_per_period: Uint128::from(1000u128),
+            period_seconds: period,
+            expires: None,
+        };
+        let _info = mock_info("alice", &[]);
+        execute(deps.as_mut(), env.clone(), _info, _msg).unwrap();
+
+        // and one with a periodic allowance gets no cw20 one
+        let _msg = ExecuteMsg::IncreaseAllowance {
+            spender: "fund".to_string(),
+            amount: Uint128::from(50u128),
+            expires: None,
+        };
+        let _info = mock_info("alice", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), _info, _msg);
+        match _res.unwrap_err() {
+            ContractError::Std(GenericErr { msg, .. }) => {
+                assert_eq!(msg, "Spender already has a periodic allowance")
+            }
+            _ => panic!("Must return generic error"),
+        }
+
+        let _msg = ExecuteMsg::TransferFrom {
+            owner: "alice".to_string(),
+            recipient: "fund".to_string(),
+            amount: Uint128::from(600u128),
+        };
+        let _info = mock_info("fund", &[]);
+        execute(deps.as_mut(), at(100), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::BurnFrom {
+            owner: "alice".to_string(),
+            amount: Uint128::from(400u128),
+            reason: None,
+        };
+        let _info = mock_info("fund", &[]);
+        execute(deps.as_mut(), at(200), _info, _msg).unwrap();
+
+        // the period's amount is used up
+        let _msg = ExecuteMsg::TransferFrom {
+            owner: "alice".to_string(),
+            recipient: "fund".to_string(),
+            amount: Uint128::from(1u128),
+        };
+        let _info = mock_info("fund", &[]);
+        let _res = execute(deps.as_mut(), at(300), _info, _msg);
+        match _res.unwrap_err() {
+            ContractError::Std(GenericErr { msg, .. }) => {
+                assert_eq!(msg, "Periodic allowance exceeded")
+            }
+            _ => panic!("Must return generic error"),
+        }
+        assert_eq!(
+            query_allowance(deps.as_ref(), "alice".to_string(), "fund".to_string())
+                .unwrap()
+                .allowance,
+            Uint128::zero()
+        );
+
+        // and refills with the next period
+        let _res = query_periodic_allowance(
+            deps.as_ref(),
+            at(period + 300),
+            "alice".to_string(),
+            "fund".to_string(),
+        )
+        .unwrap();
+        assert_eq!(_res.period_start, env.block.time.seconds() + period);
+        assert_eq!(_res.spent, Uint128::zero());
+        assert_eq!(_res.available, Uint128::from(1000u128));
+
+        let _msg = ExecuteMsg::TransferFrom {
+            owner: "alice".to_string(),
+            recipient: "fund".to_string(),
+            amount: Uint128::from(1000u128),
+        };
+        let _info = mock_info("fund", &[]);
+        execute(deps.as_mut(), at(period + 300), _info, _msg).unwrap();
+        assert_eq!(
+            get_balance(deps.as_ref(), "alice".to_string()),
+            Uint128::from(8000u128)
+        );
+
+        // a new period length starts a new period
+        let _msg = ExecuteMsg::IncreasePeriodicAllowance {
+            spender: "fund".to_string(),
+            amount_per_period: Uint128::from(1000u128),
+            period_seconds: 86400,
+            expires: None,
+        };
+        let _info = mock_info("alice", &[]);
+        execute(deps.as_mut(), at(period + 400), _info, _msg).unwrap();
+        let _res = query_periodic_allowance(
+            deps.as_ref(),
+            at(period + 400),
+            "alice".to_string(),
+            "fund".to_string(),
+        )
+        .unwrap();
+        assert_eq!(_res.period_start, env.block.time.seconds() + period + 400);
+        assert_eq!(_res.spent, Uint128::zero());
+        assert_eq!(_res.available, Uint128::from(2000u128));
+
+        // once the periodic allowance is removed a cw20 allowance can be set
+        let _msg = ExecuteMsg::DecreasePeriodicAllowance {
+            spender: "fund".to_string(),
+            amount_per_period: Uint128::from(2000u128),
+        };
+        let _info = mock_info("alice", &[]);
+        execute(deps.as_mut(), at(period + 400), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::IncreaseAllowance {
+            spender: "fund".to_string(),
+            amount: Uint128::from(50u128),
+            expires: None,
+        };
+        let _info = mock_info("alice", &[]);
+        execute(deps.as_mut(), at(period + 400), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::TransferFrom {
+            owner: "alice".to_string(),
+            recipient: "fund".to_string(),
+            amount: Uint128::from(50u128),
+        };
+        let _info = mock_info("fund", &[]);
+        execute(deps.as_mut(), at(period + 500), _info, _msg).unwrap();
+        assert_eq!(
+            query_allowance(deps.as_ref(), "alice".to_string(), "fund".to_string())
+                .unwrap()
+                .allowance,
+            Uint128::zero()
+        );
+    }
+
+    #[test]
+    fn test_vesting() {
+        let mut deps = mock_dependencies_with_balance(&[]);
+        let max_supply = 1000000u128;
+        let env = mock_env();
+        let now = env.block.time.seconds();
+        let at = |seconds: u64| {
+            let mut env = mock_env();
+            env.block.time = env.block.time.plus_seconds(seconds);
+            env
+        };
+
+        let instantiate_msg = default_instantiate(max_supply);
+        let _info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), env.clone(), _info, instantiate_msg).unwrap();
+
+        let _msg = ExecuteMsg::SetMinter {
+            minter: "minter".to_string(),
+            quota: Uint128::from(max_supply),
+        };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), env.clone(), _info, _msg).unwrap();
+
+        // only gov or a minter
+        let _msg = ExecuteMsg::CreateVesting {
+            recipient: "alice".to_string(),
+            amount: Uint128::from(1000u128),
+            schedule: VestingSchedule::Linear {
+                start: now,
+                end: now + 1000,
+            },
+        };
+        let _info = mock_info("alice", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), _info, _msg.clone());
+        match _res {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+
+        let _bad_msg = ExecuteMsg::CreateVesting {
+            recipient: "alice".to_string(),
+            amount: Uint128::from(1000u128),
+            schedule: VestingSchedule::Linear {
+                start: now,
+                end: now,
+            },
+        };
+        let _info = mock_info("minter", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), _info, _bad_msg);
+        match _res.unwrap_err() {
+            ContractError::Std(GenericErr { msg, .. }) => {
+                assert_eq!(msg, "Invalid vesting schedule")
+            }
+            _ => panic!("Must return generic error"),
+        }
+
+        // a minter mints the vesting amount
+        let _info = mock_info("minter", &[]);
+        execute(deps.as_mut(), env.clone(), _info, _msg.clone()).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "alice"), Uint128::from(1000u128));
+
+        let _info = mock_info("minter", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), _info, _msg);
+        match _res.unwrap_err() {
+            ContractError::Std(GenericErr { msg, .. }) => {
+                assert_eq!(msg, "Vesting account already exists")
+            }
+            _ => panic!("Must return generic error"),
+        }
+
+        let res = query_vesting_balance(deps.as_ref(), at(250), "alice".to_string()).unwrap();
+        assert_eq!(res.locked, Uint128::from(750u128));
+        assert_eq!(res.vested, Uint128::from(250u128));
+        assert_eq!(res.spendable, Uint128::from(250u128));
+
+        let _msg = ExecuteMsg::Transfer {
+            recipient: "bob".to_string(),
+            amount: Uint128::from(250u128),
+        };
+        let _info = mock_info("alice", &[]);
+        execute(deps.as_mut(), at(250), _info, _msg).unwrap();
+
+        // what was moved came out of the unlocked part
+        let res = query_vesting_balance(deps.as_ref(), at(500), "alice".to_string()).unwrap();
+        assert_eq!(res.balance, Uint128::from(750u128));
+        assert_eq!(res.spendable, Uint128::from(250u128));
+
+        // gov funds a cliff vesting from its own balance
+        let _msg = ExecuteMsg::Mint {
+            recipient: "creator".to_string(),
+            amount: Uint128::from(500u128),
+            contract: None,
+            msg: None,
+        };
+        let _info = mock_info("minter", &[]);
+        execute(deps.as_mut(), env.clone(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::CreateVesting {
+            recipient: "carol".to_string(),
+            amount: Uint128::from(500u128),
+            schedule: VestingSchedule::Cliff {
+                release_at: now + 100,
+            },
+        };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), env.clone(), _info, _msg).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "creator"), Uint128::zero());
+        assert_eq!(get_balance(deps.as_ref(), "carol"), Uint128::from(500u128));
+
+        let res = query_vesting_balance(deps.as_ref(), at(99), "carol".to_string()).unwrap();
+        assert_eq!(res.spendable, Uint128::zero());
+        assert_eq!(
+            res.schedule,
+            Some(VestingSchedule::Cliff {
+                release_at: now + 100
+            })
+        );
+
+        let _msg = ExecuteMsg::Transfer {
+            recipient: "bob".to_string(),
+            amount: Uint128::from(500u128),
+        };
+        let _info = mock_info("carol", &[]);
+        execute(deps.as_mut(), at(100), _info, _msg).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "bob"), Uint128::from(750u128));
+
+        // the locked part can't be moved
+        let _msg = ExecuteMsg::Transfer {
+            recipient: "bob".to_string(),
+            amount: Uint128::from(251u128),
+        };
+        let _info = mock_info("alice", &[]);
+        let _res = execute(deps.as_mut(), at(500), _info, _msg);
+        match _res.unwrap_err() {
+            ContractError::VestingLocked(account) => assert_eq!(account, "alice"),
+            _ => panic!("Must return vesting locked error"),
+        }
+    }
+
+    #[test]
+    fn test_emission() {
+        let mut deps = mock_dependencies_with_balance(&[]);
+        let max_supply = 1000000u128;
+        let env = mock_env();
+        let now = env.block.time.seconds();
+        let at = |seconds: u64| {
+            let mut env = mock_env();
+            env.block.time = env.block.time.plus_seconds(seconds);
+            env
+        };
+
+        let instantiate_msg = default_instantiate(max_supply);
+        let _info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), env.clone(), _info, instantiate_msg).unwrap();
+
+        let _info = mock_info("anyone", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), _info, ExecuteMsg::Emit {});
+        match _res.unwrap_err() {
+            ContractError::Std(GenericErr { msg, .. }) => {
+                assert_eq!(msg, "Emission schedule not set")
+            }
+            _ => panic!("Must return generic error"),
+        }
+
+        let receivers = vec![
+            EmissionReceiver {
+                address: "alice".to_string(),
+                weight: 3,
+            },
+            EmissionReceiver {
+                address: "bob".to_string(),
+                weight: 1,
+            },
+        ];
+        let _msg = ExecuteMsg::SetEmissionSchedule {
+            curve: EmissionCurve::Piecewise {
+                segments: vec![
+                    EmissionSegment {
+                        start: now,
+                        rate_per_second: Uint128::from(10u128),
+                    },
+                    EmissionSegment {
+                        start: now + 100,
+                        rate_per_second: Uint128::from(5u128),
+                    },
+                ],
+            },
+            receivers: receivers.clone(),
+        };
+        let _info = mock_info("alice", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), _info, _msg.clone());
+        match _res {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+
+        let _bad_msg = ExecuteMsg::SetEmissionSchedule {
+            curve: EmissionCurve::Piecewise {
+                segments: vec![
+                    EmissionSegment {
+                        start: now + 100,
+                        rate_per_second: Uint128::from(5u128),
+                    },
+                    EmissionSegment {
+                        start: now,
+                        rate_per_second: Uint128::from(10u128),
+                    },
+                ],
+            },
+            receivers: receivers.clone(),
+        };
+        let _info = mock_info("creator", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), _info, _bad_msg);
+        match _res.unwrap_err() {
+            ContractError::Std(GenericErr { msg, .. }) => assert_eq!(msg, "Invalid emission curve"),
+            _ => panic!("Must return generic error"),
+        }
+
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), env.clone(), _info, _msg).unwrap();
+
+        let res = query_projected_supply(deps.as_ref(), env.clone(), now + 200).unwrap();
+        assert_eq!(res.supply, Uint128::from(1500u128));
+        assert!(query_projected_supply(deps.as_ref(), env.clone(), now - 1).is_err());
+
+        // permissionless, split by weight
+        let _info = mock_info("anyone", &[]);
+        execute(deps.as_mut(), at(50), _info, ExecuteMsg::Emit {}).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "alice"), Uint128::from(375u128));
+        assert_eq!(get_balance(deps.as_ref(), "bob"), Uint128::from(125u128));
+
+        let _info = mock_info("anyone", &[]);
+        execute(deps.as_mut(), at(50), _info, ExecuteMsg::Emit {}).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "alice"), Uint128::from(375u128));
+
+        let res = query_emission_schedule(deps.as_ref(), at(150))
+            .unwrap()
+            .unwrap();
+        assert_eq!(res.last_emitted, now + 50);
+        assert_eq!(res.pending, Uint128::from(750u128));
+
+        // replacing the schedule settles the old one, the last receiver gets the dust
+        let _msg = ExecuteMsg::SetEmissionSchedule {
+            curve: EmissionCurve::Halving {
+                start: now + 150,
+                initial_rate: Uint128::from(8u128),
+                epoch_seconds: 10,
+            },
+            receivers: vec![EmissionReceiver {
+                address: "alice".to_string(),
+                weight: 1,
+            }],
+        };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), at(150), _info, _msg).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "alice"), Uint128::from(937u128));
+        assert_eq!(get_balance(deps.as_ref(), "bob"), Uint128::from(313u128));
+
+        // 8 * 10 + 4 * 10 + 2 * 10 + 1 * 10
+        let res = query_projected_supply(deps.as_ref(), at(150), now + 1000).unwrap();
+        assert_eq!(res.supply, Uint128::from(1400u128));
+        let _info = mock_info("anyone", &[]);
+        execute(deps.as_mut(), at(1000), _info, ExecuteMsg::Emit {}).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "alice"), Uint128::from(1087u128));
+
+        // emission stops at max_supply
+        let _msg = ExecuteMsg::LowerMaxSupply { max_supply: 1500 };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), at(1000), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::SetEmissionSchedule {
+            curve: EmissionCurve::Piecewise {
+                segments: vec![EmissionSegment {
+                    start: now + 1000,
+                    rate_per_second: Uint128::from(100u128),
+                }],
+            },
+            receivers,
+        };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), at(1000), _info, _msg).unwrap();
+
+        let res = query_projected_supply(deps.as_ref(), at(1000), now + 2000).unwrap();
+        assert_eq!(res.supply, Uint128::from(1500u128));
+        let _info = mock_info("anyone", &[]);
+        execute(deps.as_mut(), at(1010), _info, ExecuteMsg::Emit {}).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "alice"), Uint128::from(1162u128));
+        assert_eq!(get_balance(deps.as_ref(), "bob"), Uint128::from(338u128));
+    }
+
+    // alice holds 10000, transfers are limited to 1000, wallets to 1500 and a 60s cooldown
+    fn launch_limits_deps() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies_with_balance(&[]);
+        let max_supply = 1000000u128;
+        let env = mock_env();
+
+        let instantiate_msg = default_instantiate(max_supply);
+        let _info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), env.clone(), _info, instantiate_msg).unwrap();
+
+        let _msg = ExecuteMsg::SetMinter {
+            minter: "minter".to_string(),
+            quota: Uint128::from(max_supply),
+        };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), env.clone(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::Mint {
+            recipient: "alice".to_string(),
+            amount: Uint128::from(10000u128),
+            contract: None,
+            msg: None,
+        };
+        let _info = mock_info("minter", &[]);
+        execute(deps.as_mut(), env.clone(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::SetLaunchLimits {
+            max_transfer_amount: Some(Uint128::from(1000u128)),
+            max_wallet_balance: Some(Uint128::from(1500u128)),
+            transfer_cooldown_seconds: 60,
+        };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), env, _info, _msg).unwrap();
+        deps
+    }
+
+    #[test]
+    fn test_launch_limits() {
+        let now = mock_env().block.time.seconds();
+        let at = |seconds: u64| {
+            let mut env = mock_env();
+            env.block.time = env.block.time.plus_seconds(seconds);
+            env
+        };
+        let transfer = |recipient: &str, amount: u128| ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::from(amount),
+        };
+
+        let mut deps = launch_limits_deps();
+        let _msg = ExecuteMsg::LiftLaunchLimits {};
+        let _info = mock_info("alice", &[]);
+        let _res = execute(deps.as_mut(), at(0), _info, _msg);
+        match _res {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+        let res = query_launch_limits(deps.as_ref()).unwrap();
+        assert_eq!(res.max_transfer_amount, Some(Uint128::from(1000u128)));
+        assert_eq!(res.transfer_cooldown_seconds, 60);
+        assert!(!res.lifted);
+
+        let _info = mock_info("alice", &[]);
+        execute(deps.as_mut(), at(0), _info, transfer("bob", 1000)).unwrap();
+
+        // exempt recipients skip every limit
+        let _info = mock_info("alice", &[]);
+        execute(deps.as_mut(), at(10), _info, transfer("creator", 2000)).unwrap();
+        let _msg = ExecuteMsg::SetLimitExempt {
+            account: "pool".to_string(),
+            exempt: true,
+        };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), at(10), _info, _msg).unwrap();
+        let _info = mock_info("alice", &[]);
+        execute(deps.as_mut(), at(20), _info, transfer("pool", 2000)).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "alice"), Uint128::from(5000u128));
+
+        let _info = mock_info("alice", &[]);
+        let _res = execute(deps.as_mut(), at(30), _info, transfer("carol", 100));
+        match _res.unwrap_err() {
+            ContractError::TransferCooldown(until) => assert_eq!(until, now + 60),
+            _ => panic!("Must return transfer cooldown error"),
+        }
+
+        let mut deps = launch_limits_deps();
+        let _info = mock_info("alice", &[]);
+        let _res = execute(deps.as_mut(), at(0), _info, transfer("bob", 1001));
+        match _res.unwrap_err() {
+            ContractError::MaxTransferExceeded(max) => assert_eq!(max, Uint128::from(1000u128)),
+            _ => panic!("Must return max transfer error"),
+        }
+
+        let mut deps = launch_limits_deps();
+        let _info = mock_info("alice", &[]);
+        execute(deps.as_mut(), at(0), _info, transfer("bob", 1000)).unwrap();
+        let _info = mock_info("alice", &[]);
+        let _res = execute(deps.as_mut(), at(60), _info, transfer("bob", 600));
+        match _res.unwrap_err() {
+            ContractError::MaxWalletExceeded(account) => assert_eq!(account, "bob"),
+            _ => panic!("Must return max wallet error"),
+        }
+
+        // tokens paid out by the contract are held to the wallet limit
+        let mut deps = launch_limits_deps();
+        let _info = mock_info("alice", &[]);
+        execute(deps.as_mut(), at(0), _info, transfer("bob", 1000)).unwrap();
+        let _msg = ExecuteMsg::TransferLocked {
+            recipient: "bob".to_string(),
+            amount: Uint128::from(600u128),
+            release_at: now + 160,
+            cancelable: false,
+        };
+        let _info = mock_info("alice", &[]);
+        execute(deps.as_mut(), at(60), _info, _msg).unwrap();
+        let _info = mock_info("bob", &[]);
+        let _res = execute(deps.as_mut(), at(160), _info, ExecuteMsg::ClaimLocked { id: 1 });
+        match _res.unwrap_err() {
+            ContractError::MaxWalletExceeded(account) => assert_eq!(account, "bob"),
+            _ => panic!("Must return max wallet error"),
+        }
+
+        // escrows and streams are limited like a transfer to their recipient when created
+        let mut deps = launch_limits_deps();
+        let _msg = ExecuteMsg::TransferLocked {
+            recipient: "bob".to_string(),
+            amount: Uint128::from(1001u128),
+            release_at: now + 100,
+            cancelable: false,
+        };
+        let _info = mock_info("alice", &[]);
+        let _res = execute(deps.as_mut(), at(0), _info, _msg);
+        match _res.unwrap_err() {
+            ContractError::MaxTransferExceeded(max) => assert_eq!(max, Uint128::from(1000u128)),
+            _ => panic!("Must return max transfer error"),
+        }
+
+        let mut deps = launch_limits_deps();
+        let _msg = ExecuteMsg::TransferLocked {
+            recipient: "bob".to_string(),
+            amount: Uint128::from(100u128),
+            release_at: now + 100,
+            cancelable: false,
+        };
+        let _info = mock_info("alice", &[]);
+        execute(deps.as_mut(), at(0), _info, _msg).unwrap();
+        let _msg = ExecuteMsg::CreateStream {
+            recipient: "carol".to_string(),
+            amount: Uint128::from(100u128),
+            start: now + 30,
+            end: now + 130,
+        };
+        let _info = mock_info("alice", &[]);
+        let _res = execute(deps.as_mut(), at(30), _info, _msg);
+        match _res.unwrap_err() {
+            ContractError::TransferCooldown(until) => assert_eq!(until, now + 60),
+            _ => panic!("Must return transfer cooldown error"),
+        }
+
+        // every leg of a batch is limited, the batch counts as one transfer for the cooldown
+        let batch = |transfers: Vec<(&str, u128)>| ExecuteMsg::TransferBatch {
+            transfers: transfers
+                .into_iter()
+                .map(|(recipient, amount)| (recipient.to_string(), Uint128::from(amount)))
+                .collect(),
+        };
+        let mut deps = launch_limits_deps();
+        let _info = mock_info("alice", &[]);
+        let _msg = batch(vec![("bob", 1000), ("carol", 1000)]);
+        execute(deps.as_mut(), at(0), _info, _msg).unwrap();
+        let _info = mock_info("alice", &[]);
+        let _res = execute(deps.as_mut(), at(30), _info, batch(vec![("dave", 100)]));
+        match _res.unwrap_err() {
+            ContractError::TransferCooldown(until) => assert_eq!(until, now + 60),
+            _ => panic!("Must return transfer cooldown error"),
+        }
+
+        let mut deps = launch_limits_deps();
+        let _info = mock_info("alice", &[]);
+        let _res = execute(
+            deps.as_mut(),
+            at(0),
+            _info,
+            batch(vec![("bob", 100), ("carol", 1001)]),
+        );
+        match _res.unwrap_err() {
+            ContractError::MaxTransferExceeded(max) => assert_eq!(max, Uint128::from(1000u128)),
+            _ => panic!("Must return max transfer error"),
+        }
+
+        let mut deps = launch_limits_deps();
+        let _info = mock_info("alice", &[]);
+        let _res = execute(
+            deps.as_mut(),
+            at(0),
+            _info,
+            batch(vec![("bob", 800), ("bob", 800)]),
+        );
+        match _res.unwrap_err() {
+            ContractError::MaxWalletExceeded(account) => assert_eq!(account, "bob"),
+            _ => panic!("Must return max wallet error"),
+        }
+
+        // lifting is permanent
+        let mut deps = launch_limits_deps();
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), at(0), _info, ExecuteMsg::LiftLaunchLimits {}).unwrap();
+        let _info = mock_info("alice", &[]);
+        execute(deps.as_mut(), at(0), _info, transfer("bob", 5000)).unwrap();
+        let _info = mock_info("alice", &[]);
+        execute(deps.as_mut(), at(1), _info, transfer("bob", 5000)).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "bob"), Uint128::from(10000u128));
+
+        let _msg = ExecuteMsg::SetLaunchLimits {
+            max_transfer_amount: Some(Uint128::from(1000u128)),
+            max_wallet_balance: None,
+            transfer_cooldown_seconds: 0,
+        };
+        let _info = mock_info("creator", &[]);
+        let _res = execute(deps.as_mut(), at(2), _info, _msg);
+        match _res.unwrap_err() {
+            ContractError::Std(GenericErr { msg, .. }) => assert_eq!(msg, "Launch limits lifted"),
+            _ => panic!("Must return generic error"),
+        }
+        assert!(query_launch_limits(deps.as_ref()).unwrap().lifted);
+    }
+
+    #[test]
+    fn test_mint_approval() {
+        let mut deps = mock_dependencies_with_balance(&[]);
+        let max_supply = 1000000u128;
+        let env = mock_env();
+        let at = |seconds: u64| {
+            let mut env = mock_env();
+            env.block.time = env.block.time.plus_seconds(seconds);
+            env
+        };
+        let mint = |recipient: &str, amount: u128| ExecuteMsg::Mint {
+            recipient: recipient.to_string(),
+            amount: Uint128::from(amount),
+            contract: None,
+            msg: None,
+        };
+
+        let instantiate_msg = default_instantiate(max_supply);
+        let _info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), env.clone(), _info, instantiate_msg).unwrap();
+
+        let _msg = ExecuteMsg::SetMinter {
+            minter: "minter".to_string(),
+            quota: Uint128::from(max_supply),
+        };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), env.clone(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::SetMintApproval {
+            threshold: Some(Uint128::from(1000u128)),
+            window_seconds: 3600,
+        };
+        let _info = mock_info("minter", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), _info, _msg.clone());
+        match _res {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), env.clone(), _info, _msg).unwrap();
+
+        // routine mints go through
+        let _info = mock_info("minter", &[]);
+        execute(deps.as_mut(), env.clone(), _info, mint("alice", 1000)).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "alice"), Uint128::from(1000u128));
+
+        let _info = mock_info("minter", &[]);
+        execute(deps.as_mut(), env.clone(), _info, mint("bob", 5000)).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "bob"), Uint128::zero());
+        let res = query_mint_proposals(deps.as_ref(), env.clone(), None, None).unwrap();
+        assert_eq!(res.proposals.len(), 1);
+        assert_eq!(res.proposals[0].id, 1);
+        assert_eq!(res.proposals[0].recipient, Addr::unchecked("bob"));
+        assert_eq!(res.proposals[0].expires_at, env.block.time.seconds() + 3600);
+        assert!(!res.proposals[0].expired);
+
+        let _msg = ExecuteMsg::MintBatch {
+            mints: vec![
+                ("alice".to_string(), Uint128::from(1000u128)),
+                ("bob".to_string(), Uint128::from(500u128)),
+            ],
+        };
+        let _info = mock_info("minter", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), _info, _msg);
+        match _res.unwrap_err() {
+            ContractError::Std(GenericErr { msg, .. }) => {
+                assert_eq!(msg, "Mint above approval threshold")
+            }
+            _ => panic!("Must return generic error"),
+        }
+
+        let _info = mock_info("minter", &[]);
+        let _res = execute(deps.as_mut(), at(10), _info, ExecuteMsg::ApproveMint { id: 1 });
+        match _res {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), at(10), _info, ExecuteMsg::ApproveMint { id: 1 }).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "bob"), Uint128::from(5000u128));
+        assert!(query_mint_proposal(deps.as_ref(), at(10), 1).is_err());
+
+        let _info = mock_info("minter", &[]);
+        execute(deps.as_mut(), env.clone(), _info, mint("bob", 2000)).unwrap();
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), env.clone(), _info, ExecuteMsg::RejectMint { id: 2 }).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "bob"), Uint128::from(5000u128));
+        let res = query_minters(deps.as_ref(), None, None).unwrap();
+        assert_eq!(res.minters[0].minted, Uint128::from(6000u128));
+
+        // gov has window_seconds to approve, the proposal holds its quota meanwhile
+        let _info = mock_info("minter", &[]);
+        execute(deps.as_mut(), env.clone(), _info, mint("bob", 3000)).unwrap();
+        let res = query_minters(deps.as_ref(), None, None).unwrap();
+        assert_eq!(res.minters[0].minted, Uint128::from(9000u128));
+        assert!(query_mint_proposal(deps.as_ref(), at(3600), 3).unwrap().expired);
+        let _info = mock_info("creator", &[]);
+        let _res = execute(deps.as_mut(), at(3600), _info, ExecuteMsg::ApproveMint { id: 3 });
+        match _res.unwrap_err() {
+            ContractError::Std(GenericErr { msg, .. }) => assert_eq!(msg, "Mint proposal expired"),
+            _ => panic!("Must return generic error"),
+        }
+
+        // anyone can clear an expired proposal
+        let _info = mock_info("alice", &[]);
+        execute(deps.as_mut(), at(3600), _info, ExecuteMsg::RejectMint { id: 3 }).unwrap();
+        assert!(query_mint_proposal(deps.as_ref(), at(3600), 3).is_err());
+        let res = query_minters(deps.as_ref(), None, None).unwrap();
+        assert_eq!(res.minters[0].minted, Uint128::from(6000u128));
+
+        // mints add up within a window
+        let _info = mock_info("minter", &[]);
+        execute(deps.as_mut(), at(3600), _info, mint("alice", 600)).unwrap();
+        let _info = mock_info("minter", &[]);
+        execute(deps.as_mut(), at(3600), _info, mint("alice", 600)).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "alice"), Uint128::from(1600u128));
+        let res = query_mint_proposals(deps.as_ref(), at(3600), None, None).unwrap();
+        assert_eq!(res.proposals.len(), 1);
+        assert_eq!(res.proposals[0].id, 4);
+        assert_eq!(res.proposals[0].amount, Uint128::from(600u128));
+
+        let _info = mock_info("alice", &[]);
+        let _res = execute(deps.as_mut(), at(3600), _info, ExecuteMsg::RejectMint { id: 4 });
+        match _res {
+            Err(ContractError::Unauthorized {}) => {}
+            _ => panic!("Must return unauthorized error"),
+        }
+
+        // and start over with the next one
+        let _info = mock_info("minter", &[]);
+        execute(deps.as_mut(), at(7200), _info, mint("alice", 1000)).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "alice"), Uint128::from(2600u128));
+    }
+
+    #[test]
+    fn test_payment_references() {
+        let mut deps = mock_dependencies_with_balance(&[]);
+        let max_supply = 1000000u128;
+        let env = mock_env();
+
+        let instantiate_msg = default_instantiate(max_supply);
+        let _info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), env.clone(), _info, instantiate_msg).unwrap();
+
+        let _msg = ExecuteMsg::SetMinter {
+            minter: "minter".to_string(),
+            quota: Uint128::from(max_supply),
+        };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), env.clone(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::Mint {
+            recipient: "alice".to_string(),
+            amount: Uint128::from(10000u128),
+            contract: None,
+            msg: None,
+        };
+        let _info = mock_info("minter", &[]);
+        execute(deps.as_mut(), env.clone(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::UpdateTransferTax {
+            tax_bps: Some(100),
+            burn_bps: Some(10000),
+            receiver: None,
+        };
+        let _info = mock_info("creator", &[]);
+        execute(deps.as_mut(), env.clone(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::TransferWithReference {
+            recipient: "shop".to_string(),
+            amount: Uint128::from(1000u128),
+            reference: "".to_string(),
+        };
+        let _info = mock_info("alice", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), _info, _msg);
+        match _res.unwrap_err() {
+            ContractError::Std(GenericErr { msg, .. }) => {
+                assert_eq!(msg, "Invalid payment reference")
+            }
+            _ => panic!("Must return generic error"),
+        }
+
+        let _msg = ExecuteMsg::TransferWithReference {
+            recipient: "shop".to_string(),
+            amount: Uint128::from(1000u128),
+            reference: "invoice-1".to_string(),
+        };
+        let _info = mock_info("alice", &[]);
+        execute(deps.as_mut(), env.clone(), _info, _msg).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), "shop"), Uint128::from(990u128));
+
+        // plain transfers are not listed
+        let _msg = ExecuteMsg::Transfer {
+            recipient: "shop".to_string(),
+            amount: Uint128::from(100u128),
+        };
+        let _info = mock_info("alice", &[]);
+        execute(deps.as_mut(), env.clone(), _info, _msg).unwrap();
+
+        let _msg = ExecuteMsg::SendWithReference {
+            contract: "shop".to_string(),
+            amount: Uint128::from(500u128),
+            msg: Binary::from(b"{}".to_vec()),
+            reference: "invoice-2".to_string(),
+        };
+        let _info = mock_info("alice", &[]);
+        let _res = execute(deps.as_mut(), env.clone(), _info, _msg).unwrap();
+        assert_eq!(_res.messages.len(), 1);
+
+        let res =
+            query_payments_received(deps.as_ref(), "shop".to_string(), None, None, None).unwrap();
+        assert_eq!(res.payments.len(), 2);
+        assert_eq!(res.payments[0].sender, Addr::unchecked("alice"));
+        assert_eq!(res.payments[0].amount, Uint128::from(1000u128));
+        assert_eq!(res.payments[0].net_amount, Uint128::from(990u128));
+        assert_eq!(res.payments[1].reference, "invoice-2");
+
+        let res = query_payments_received(
+            deps.as_ref(),
+            "shop".to_string(),
+            Some("invoice-2".to_string()),
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(res.payments.len(), 1);
+        assert_eq!(res.payments[0].id, 2);
+        assert_eq!(res.payments[0].net_amount, Uint128::from(495u128));
+
+        let res = query_payments_received(deps.as_ref(), "shop".to_string(), None, Some(1), None)
+            .unwrap();
+        assert_eq!(res.payments.len(), 1);
+        assert!(query_payments_received(deps.as_ref(), "alice".to_string(), None, None, None)
+            .unwrap()
+            .payments
+            .is_empty());
+    }
 }